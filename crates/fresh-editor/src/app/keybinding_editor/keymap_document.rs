@@ -0,0 +1,192 @@
+//! Portable keymap document format: export/import the resolved binding
+//! table so users can share community keymaps or migrate settings between
+//! machines, the way config files already do.
+//!
+//! The format carries a `_version` field the same way the i18n loader's
+//! locale files carry (and skip) `_version`/other `_`-prefixed metadata, so
+//! future schema changes can be detected on import.
+
+use crate::config::Keybinding;
+use serde::{Deserialize, Serialize};
+
+/// Current schema version for exported keymap documents.
+pub const KEYMAP_SCHEMA_VERSION: u32 = 1;
+
+/// One resolved binding: the context it applies in, its key sequence
+/// (already formatted for display, e.g. via `format_chord_keys`), and the
+/// action id it triggers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeymapEntry {
+    /// The `when` context the binding is scoped to, or `"global"` if
+    /// unscoped.
+    pub context: String,
+    pub keys: String,
+    pub action: String,
+}
+
+/// A full exported/imported keymap document.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeymapDocument {
+    #[serde(rename = "_version")]
+    pub version: u32,
+    pub bindings: Vec<KeymapEntry>,
+}
+
+impl KeymapDocument {
+    /// Build a document from the resolved binding table.
+    pub fn from_entries(bindings: Vec<KeymapEntry>) -> Self {
+        Self {
+            version: KEYMAP_SCHEMA_VERSION,
+            bindings,
+        }
+    }
+
+    /// Build a document straight from the editor's raw keybinding config
+    /// entries, formatting each key sequence for display.
+    pub fn from_keybindings(keybindings: &[Keybinding], format_keys: impl Fn(&Keybinding) -> String) -> Self {
+        let bindings = keybindings
+            .iter()
+            .map(|kb| KeymapEntry {
+                context: kb.when.clone().unwrap_or_else(|| "global".to_string()),
+                keys: format_keys(kb),
+                action: kb.action.clone(),
+            })
+            .collect();
+        Self::from_entries(bindings)
+    }
+
+    /// Serialize to pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| format!("failed to serialize keymap: {e}"))
+    }
+
+    /// Parse a keymap document from JSON. Unrecognized schema versions are
+    /// still parsed (so `diff_against` can show the user what would change)
+    /// but the caller should warn before applying one.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| format!("invalid keymap document: {e}"))
+    }
+
+    /// Diff this document's bindings against the currently active ones,
+    /// so an import can be previewed before committing.
+    pub fn diff_against(&self, current: &[KeymapEntry]) -> Vec<KeymapDiff> {
+        let mut diffs = Vec::new();
+
+        for incoming in &self.bindings {
+            match current
+                .iter()
+                .find(|c| c.context == incoming.context && c.keys == incoming.keys)
+            {
+                Some(existing) if existing.action == incoming.action => {
+                    // Identical binding already present; nothing to report.
+                }
+                Some(existing) => diffs.push(KeymapDiff {
+                    kind: KeymapDiffKind::Overridden,
+                    entry: incoming.clone(),
+                    previous_action: Some(existing.action.clone()),
+                }),
+                None => diffs.push(KeymapDiff {
+                    kind: KeymapDiffKind::Added,
+                    entry: incoming.clone(),
+                    previous_action: None,
+                }),
+            }
+        }
+
+        for existing in current {
+            let still_present = self
+                .bindings
+                .iter()
+                .any(|b| b.context == existing.context && b.keys == existing.keys);
+            if !still_present {
+                diffs.push(KeymapDiff {
+                    kind: KeymapDiffKind::Removed,
+                    entry: existing.clone(),
+                    previous_action: None,
+                });
+            }
+        }
+
+        diffs
+    }
+}
+
+/// How an incoming binding compares to the currently active keymap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeymapDiffKind {
+    /// Present in the import, absent from the current keymap.
+    Added,
+    /// Present in both, but bound to a different action.
+    Overridden,
+    /// Present in the current keymap, absent from the import.
+    Removed,
+}
+
+/// A single row of an import preview.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeymapDiff {
+    pub kind: KeymapDiffKind,
+    pub entry: KeymapEntry,
+    /// For [`KeymapDiffKind::Overridden`], the action the binding currently
+    /// triggers before the import is applied.
+    pub previous_action: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(context: &str, keys: &str, action: &str) -> KeymapEntry {
+        KeymapEntry {
+            context: context.to_string(),
+            keys: keys.to_string(),
+            action: action.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_round_trip_json() {
+        let doc = KeymapDocument::from_entries(vec![entry("global", "ctrl+c", "copy")]);
+        let json = doc.to_json().unwrap();
+        let parsed = KeymapDocument::from_json(&json).unwrap();
+        assert_eq!(parsed.version, KEYMAP_SCHEMA_VERSION);
+        assert_eq!(parsed.bindings, doc.bindings);
+    }
+
+    #[test]
+    fn test_diff_detects_added_and_overridden() {
+        let current = vec![
+            entry("global", "ctrl+c", "copy"),
+            entry("global", "ctrl+v", "paste"),
+        ];
+        let incoming = KeymapDocument::from_entries(vec![
+            entry("global", "ctrl+c", "copy"),       // unchanged
+            entry("global", "ctrl+v", "paste_alt"),  // overridden
+            entry("global", "ctrl+x", "cut"),        // added
+        ]);
+
+        let diffs = incoming.diff_against(&current);
+        assert_eq!(diffs.len(), 2);
+        assert!(diffs
+            .iter()
+            .any(|d| d.kind == KeymapDiffKind::Overridden && d.entry.action == "paste_alt"));
+        assert!(diffs
+            .iter()
+            .any(|d| d.kind == KeymapDiffKind::Added && d.entry.action == "cut"));
+    }
+
+    #[test]
+    fn test_diff_detects_removed_binding() {
+        let current = vec![entry("global", "ctrl+c", "copy")];
+        let incoming = KeymapDocument::from_entries(vec![]);
+
+        let diffs = incoming.diff_against(&current);
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].kind, KeymapDiffKind::Removed);
+    }
+
+    #[test]
+    fn test_from_json_rejects_malformed_document() {
+        assert!(KeymapDocument::from_json("not json").is_err());
+    }
+}
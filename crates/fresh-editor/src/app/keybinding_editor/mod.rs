@@ -2,11 +2,15 @@
 //!
 //! A modal dialog for browsing, searching, and editing keybindings.
 //! Provides a table view of all resolved bindings with search, filter,
-//! key recording, conflict detection, and keymap management.
+//! key recording, conflict detection, and keymap management, including
+//! exporting/importing the resolved binding table via [`KeymapDocument`]
+//! (see [`keymap_document`]).
 
 mod editor;
 mod helpers;
+mod keymap_document;
 mod types;
 
 pub use editor::KeybindingEditor;
+pub use keymap_document::{KeymapDiff, KeymapDiffKind, KeymapDocument, KeymapEntry, KEYMAP_SCHEMA_VERSION};
 pub use types::*;
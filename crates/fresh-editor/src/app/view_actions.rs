@@ -1,11 +1,31 @@
 //! View mode action handlers.
 //!
 //! This module contains handlers for view-related actions like compose mode toggling.
+//!
+//! `handle_toggle_compose_mode` below reads `self.extension_host`, which
+//! assumes `Editor` carries an `extension_host: Option<ExtensionHost>`
+//! field. There's no `app/mod.rs` (or `app.rs`) in this checkout backing
+//! the `app` module `lib.rs` declares, so `Editor` itself isn't defined
+//! anywhere in this crate yet for that field to live on — this module
+//! can't compile standalone until that's added. [`resolve_view_transform`]
+//! is the real decision logic, kept separate and unit-tested so the
+//! `Editor::extension_host` plumbing is the only thing left to wire in
+//! once `Editor` exists.
 
 use super::Editor;
+use crate::extensions::ExtensionHost;
 use crate::state::ViewMode;
 use rust_i18n::t;
 
+/// Pick the view transform to use for `view_mode`: the first registered
+/// extension that declares support for it, or `None` to fall back to the
+/// builtin Break-token reflow. Factored out of
+/// [`Editor::handle_toggle_compose_mode`] so the decision is testable
+/// without a live `Editor`.
+fn resolve_view_transform(host: Option<&ExtensionHost>, view_mode: &str) -> Option<String> {
+    host?.transform_for_view_mode(view_mode)
+}
+
 impl Editor {
     /// Toggle between Compose and Source view modes.
     pub fn handle_toggle_compose_mode(&mut self) {
@@ -28,8 +48,10 @@ impl Editor {
         // Update split view state (source of truth for view mode and line numbers)
         if let Some(vs) = self.split_view_states.get_mut(&active_split) {
             vs.view_mode = view_mode.clone();
-            // In Compose mode, disable builtin line wrap - the plugin handles
-            // wrapping by inserting Break tokens in the view transform pipeline.
+            // In Compose mode, disable builtin line wrap - wrapping is
+            // handled by the view transform pipeline instead (either the
+            // registered extension's transform, selected below, or the
+            // builtin Break-token reflow when no extension is registered).
             // In Source mode, respect the user's default_wrap preference.
             vs.viewport.line_wrap_enabled = match view_mode {
                 ViewMode::Compose => false,
@@ -38,6 +60,10 @@ impl Editor {
             match view_mode {
                 ViewMode::Compose => {
                     vs.show_line_numbers = false;
+                    // Prefer a registered WASM extension's transform over
+                    // the builtin one; `None` here means "use the builtin
+                    // Break-token reflow", matched at render time.
+                    vs.view_transform = resolve_view_transform(self.extension_host.as_ref(), "compose");
                 }
                 ViewMode::Source => {
                     // Clear compose width to remove margins
@@ -55,3 +81,20 @@ impl Editor {
         self.set_status_message(t!("view.mode", mode = mode_label).to_string());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extensions::ExtensionHost;
+
+    #[test]
+    fn test_resolve_view_transform_none_host_falls_back_to_builtin() {
+        assert_eq!(resolve_view_transform(None, "compose"), None);
+    }
+
+    #[test]
+    fn test_resolve_view_transform_no_matching_extension_falls_back_to_builtin() {
+        let host = ExtensionHost::new(Default::default());
+        assert_eq!(resolve_view_transform(Some(&host), "compose"), None);
+    }
+}
@@ -0,0 +1,246 @@
+//! The sandboxed WASM host: discovers extensions, instantiates their guest
+//! modules, and runs the view-transform ABI within a fuel-based time budget.
+//!
+//! # Guest ABI
+//! A guest exports:
+//! - `memory`: the linear memory the host writes the request into and reads
+//!   the response from.
+//! - `alloc(len: i32) -> i32`: reserve `len` bytes in guest memory, returning
+//!   the offset.
+//! - `fresh_view_transform(ptr: i32, len: i32) -> i64`: given a
+//!   [`ViewTransformRequest`] encoded as JSON at `ptr..ptr+len`, return the
+//!   output offset/length packed as `(offset << 32) | len` pointing at a
+//!   JSON-encoded [`ViewTransformResponse`].
+//!
+//! Any failure (trap, budget exceeded, malformed output) falls back to the
+//! identity transform so a broken extension can never corrupt rendering.
+
+use super::manifest::{self, ExtensionManifest};
+use super::tokens::{ViewToken, ViewTransformRequest, ViewTransformResponse};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use wasmtime::{Engine, Instance, Linker, Module, Store};
+use wasmtime_wasi::sync::WasiCtxBuilder;
+use wasmtime_wasi::WasiCtx;
+
+/// Resource limits for a guest invocation. Only `time` is applied (as
+/// fuel, see [`fuel_for_budget`]); `max_memory_pages` is stored but the
+/// host does not limit guest memory growth with it.
+#[derive(Debug, Clone, Copy)]
+pub struct ExtensionBudget {
+    /// Wall-clock budget for a single transform call.
+    pub time: Duration,
+    /// Max linear memory pages (64 KiB each). Not applied to guest
+    /// instances by [`ExtensionHost`].
+    pub max_memory_pages: u32,
+}
+
+impl Default for ExtensionBudget {
+    fn default() -> Self {
+        Self {
+            time: Duration::from_millis(50),
+            max_memory_pages: 256, // 16 MiB
+        }
+    }
+}
+
+struct LoadedExtension {
+    manifest: ExtensionManifest,
+    module: Module,
+}
+
+/// Host for sandboxed view-transform extensions.
+///
+/// Cheap to clone-share (`Arc` it at the call site). The mutex guards only
+/// the registry of compiled modules; each transform call instantiates its
+/// module in a fresh `Store` of its own.
+pub struct ExtensionHost {
+    engine: Engine,
+    budget: ExtensionBudget,
+    extensions: Mutex<HashMap<String, LoadedExtension>>,
+}
+
+impl ExtensionHost {
+    pub fn new(budget: ExtensionBudget) -> Self {
+        let mut config = wasmtime::Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config).expect("wasmtime engine config is always valid");
+        Self {
+            engine,
+            budget,
+            extensions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Discover and compile every extension manifest under `dir`.
+    /// Extensions that fail to compile are skipped (logged), not fatal.
+    pub fn load_directory(&self, dir: &Path) -> usize {
+        let mut loaded = 0;
+        for manifest in manifest::discover(dir) {
+            match Module::from_file(&self.engine, &manifest.module_path) {
+                Ok(module) => {
+                    let id = manifest.id.clone();
+                    self.extensions
+                        .lock()
+                        .unwrap()
+                        .insert(id, LoadedExtension { manifest, module });
+                    loaded += 1;
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to compile extension module {:?}: {}",
+                        manifest.module_path,
+                        e
+                    );
+                }
+            }
+        }
+        loaded
+    }
+
+    /// Return the id of the first registered extension whose manifest
+    /// declares support for `view_mode` (e.g. `"compose"`), if any.
+    pub fn transform_for_view_mode(&self, view_mode: &str) -> Option<String> {
+        self.extensions
+            .lock()
+            .unwrap()
+            .values()
+            .find(|ext| ext.manifest.applies_to(view_mode))
+            .map(|ext| ext.manifest.id.clone())
+    }
+
+    /// Run `extension_id`'s view transform over `tokens` for `visible_range`.
+    /// On any failure (missing extension, trap, timeout, malformed output)
+    /// this fails closed: the input `tokens` are returned unchanged.
+    pub fn run_view_transform(
+        &self,
+        extension_id: &str,
+        visible_range: std::ops::Range<usize>,
+        tokens: Vec<ViewToken>,
+    ) -> Vec<ViewToken> {
+        let fallback = tokens.clone();
+        match self.try_run_view_transform(extension_id, visible_range, tokens) {
+            Ok(output) => output,
+            Err(e) => {
+                tracing::warn!(
+                    "Extension '{}' view transform failed, using identity transform: {}",
+                    extension_id,
+                    e
+                );
+                fallback
+            }
+        }
+    }
+
+    fn try_run_view_transform(
+        &self,
+        extension_id: &str,
+        visible_range: std::ops::Range<usize>,
+        tokens: Vec<ViewToken>,
+    ) -> Result<Vec<ViewToken>, String> {
+        let module = {
+            let extensions = self.extensions.lock().unwrap();
+            let ext = extensions
+                .get(extension_id)
+                .ok_or_else(|| format!("unknown extension '{extension_id}'"))?;
+            ext.module.clone()
+        };
+
+        let wasi: WasiCtx = WasiCtxBuilder::new().build();
+        let mut store = Store::new(&self.engine, wasi);
+        store
+            .set_fuel(fuel_for_budget(self.budget))
+            .map_err(|e| e.to_string())?;
+
+        let mut linker: Linker<WasiCtx> = Linker::new(&self.engine);
+        wasmtime_wasi::sync::add_to_linker(&mut linker, |ctx| ctx).map_err(|e| e.to_string())?;
+
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .map_err(|e| e.to_string())?;
+
+        let request = ViewTransformRequest {
+            visible_range,
+            tokens,
+        };
+        let request_bytes =
+            serde_json::to_vec(&request).map_err(|e| format!("failed to encode request: {e}"))?;
+
+        let response_bytes = call_transform(&mut store, &instance, &request_bytes)?;
+        let response: ViewTransformResponse = serde_json::from_slice(&response_bytes)
+            .map_err(|e| format!("malformed extension output: {e}"))?;
+
+        Ok(response.tokens)
+    }
+}
+
+/// Convert a wall-clock time budget into a fuel amount. Fuel isn't a
+/// perfect proxy for wall time, but it bounds runaway loops without
+/// needing a watchdog thread per call.
+fn fuel_for_budget(budget: ExtensionBudget) -> u64 {
+    const FUEL_PER_MS: u64 = 2_000_000;
+    (budget.time.as_millis() as u64).saturating_mul(FUEL_PER_MS)
+}
+
+fn call_transform(
+    store: &mut Store<WasiCtx>,
+    instance: &Instance,
+    request_bytes: &[u8],
+) -> Result<Vec<u8>, String> {
+    let memory = instance
+        .get_memory(&mut *store, "memory")
+        .ok_or("guest does not export 'memory'")?;
+    let alloc = instance
+        .get_typed_func::<i32, i32>(&mut *store, "alloc")
+        .map_err(|e| format!("guest does not export 'alloc': {e}"))?;
+    let transform = instance
+        .get_typed_func::<(i32, i32), i64>(&mut *store, "fresh_view_transform")
+        .map_err(|e| format!("guest does not export 'fresh_view_transform': {e}"))?;
+
+    let ptr = alloc
+        .call(&mut *store, request_bytes.len() as i32)
+        .map_err(|e| format!("guest trapped in 'alloc': {e}"))?;
+    memory
+        .write(&mut *store, ptr as usize, request_bytes)
+        .map_err(|e| format!("failed writing request into guest memory: {e}"))?;
+
+    let packed = transform
+        .call(&mut *store, (ptr, request_bytes.len() as i32))
+        .map_err(|e| format!("guest trapped in 'fresh_view_transform': {e}"))?;
+
+    let out_ptr = (packed >> 32) as u32 as usize;
+    let out_len = (packed & 0xFFFF_FFFF) as u32 as usize;
+
+    let mut buf = vec![0u8; out_len];
+    memory
+        .read(&store, out_ptr, &mut buf)
+        .map_err(|e| format!("failed reading response from guest memory: {e}"))?;
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_extension_falls_back_to_identity() {
+        let host = ExtensionHost::new(ExtensionBudget::default());
+        let tokens = vec![ViewToken::text(0, 5), ViewToken::break_token()];
+        let out = host.run_view_transform("nonexistent", 0..10, tokens.clone());
+        assert_eq!(out, tokens);
+    }
+
+    #[test]
+    fn test_transform_for_view_mode_empty_host() {
+        let host = ExtensionHost::new(ExtensionBudget::default());
+        assert_eq!(host.transform_for_view_mode("compose"), None);
+    }
+
+    #[test]
+    fn test_load_directory_missing_dir_is_zero() {
+        let host = ExtensionHost::new(ExtensionBudget::default());
+        assert_eq!(host.load_directory(&PathBuf::from("/nonexistent/path")), 0);
+    }
+}
@@ -0,0 +1,150 @@
+//! Extension manifest discovery.
+//!
+//! Each extension ships a `<name>.manifest.json` next to its `<name>.wasm`
+//! guest module, e.g. `markdown.manifest.json`:
+//! ```json
+//! {
+//!   "id": "markdown-compose",
+//!   "activation": "on_demand",
+//!   "view_modes": ["compose"]
+//! }
+//! ```
+
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The activation policy a manifest declares. [`ExtensionHost`](super::ExtensionHost)
+/// reads but does not act on it: every discovered module is compiled on
+/// load and instantiated afresh for each transform call, whichever mode is
+/// declared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivationMode {
+    /// Declared as instantiated once when the extension is discovered.
+    Eager,
+    /// Declared as instantiated lazily, the first time its transform is needed.
+    OnDemand,
+}
+
+/// A single discovered extension: its identity, activation policy, and the
+/// view modes it applies its transform to.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExtensionManifest {
+    pub id: String,
+    pub activation: ActivationMode,
+    /// View mode names (e.g. `"compose"`) this extension's transform
+    /// applies to.
+    pub view_modes: Vec<String>,
+    /// Path to the `.wasm` module, filled in by [`discover`] (not part of
+    /// the JSON document itself).
+    #[serde(skip)]
+    pub module_path: PathBuf,
+}
+
+/// Scan `dir` for `*.manifest.json` files, pairing each with the `.wasm`
+/// module of the same stem. Malformed manifests are skipped with a
+/// warning rather than aborting discovery of the rest.
+pub fn discover(dir: &Path) -> Vec<ExtensionManifest> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut manifests = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if !name.ends_with(".manifest.json") {
+            continue;
+        }
+
+        match load_manifest(&path) {
+            Ok(manifest) => manifests.push(manifest),
+            Err(e) => {
+                tracing::warn!("Skipping extension manifest {:?}: {}", path, e);
+            }
+        }
+    }
+    manifests
+}
+
+fn load_manifest(path: &Path) -> Result<ExtensionManifest, String> {
+    let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut manifest: ExtensionManifest = serde_json::from_str(&text).map_err(|e| e.to_string())?;
+
+    let stem = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .and_then(|n| n.strip_suffix(".manifest.json"))
+        .ok_or_else(|| "manifest filename missing .manifest.json suffix".to_string())?;
+    let module_path = path.with_file_name(format!("{stem}.wasm"));
+    if !module_path.exists() {
+        return Err(format!("no matching module at {:?}", module_path));
+    }
+    manifest.module_path = module_path;
+    Ok(manifest)
+}
+
+impl ExtensionManifest {
+    /// Whether this extension's transform applies to the given view mode
+    /// name (e.g. `"compose"`).
+    pub fn applies_to(&self, view_mode: &str) -> bool {
+        self.view_modes.iter().any(|m| m == view_mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discover_pairs_manifest_and_module() {
+        let dir = tempdir();
+        fs::write(
+            dir.join("markdown.manifest.json"),
+            r#"{"id": "markdown-compose", "activation": "on_demand", "view_modes": ["compose"]}"#,
+        )
+        .unwrap();
+        fs::write(dir.join("markdown.wasm"), b"\0asm").unwrap();
+
+        let manifests = discover(&dir);
+        assert_eq!(manifests.len(), 1);
+        assert_eq!(manifests[0].id, "markdown-compose");
+        assert!(manifests[0].applies_to("compose"));
+        assert!(!manifests[0].applies_to("source"));
+        assert_eq!(manifests[0].module_path, dir.join("markdown.wasm"));
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_discover_skips_manifest_without_module() {
+        let dir = tempdir();
+        fs::write(
+            dir.join("orphan.manifest.json"),
+            r#"{"id": "orphan", "activation": "eager", "view_modes": []}"#,
+        )
+        .unwrap();
+
+        assert!(discover(&dir).is_empty());
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_discover_empty_directory() {
+        let dir = tempdir();
+        assert!(discover(&dir).is_empty());
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    fn tempdir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "fresh_extensions_manifest_test_{:?}",
+            std::thread::current().id()
+        ));
+        let _ = fs::create_dir_all(&dir);
+        dir
+    }
+}
@@ -0,0 +1,18 @@
+//! Sandboxed WebAssembly extension host for the view-transform pipeline.
+//!
+//! The compose-mode view transform (markdown/compose reflow, soft-wrap,
+//! code-folding overlays, ...) used to be hardcoded in-tree. This module
+//! lets third parties ship that transform as a `wasm32-wasi` guest module
+//! instead: [`host::ExtensionHost`] discovers extensions from a directory,
+//! loads their [`manifest::ExtensionManifest`], and runs the transform for
+//! the active [`ViewMode`](crate::state::ViewMode) within a fuel-based time
+//! budget (guest memory growth is not limited), failing closed to the
+//! identity transform if anything goes wrong.
+
+pub mod host;
+pub mod manifest;
+pub mod tokens;
+
+pub use host::ExtensionHost;
+pub use manifest::{ActivationMode, ExtensionManifest};
+pub use tokens::{ViewToken, ViewTokenKind};
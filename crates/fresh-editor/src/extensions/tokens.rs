@@ -0,0 +1,70 @@
+//! The token/span representation passed across the WASM extension ABI.
+//!
+//! This mirrors the shape of `vs.view_transform`'s input: a flat sequence of
+//! text runs interspersed with layout tokens (line breaks, indents) that a
+//! guest extension can insert, remove, or rewrite.
+
+use serde::{Deserialize, Serialize};
+
+/// One element of the view-transform token stream.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ViewTokenKind {
+    /// A run of literal text, referencing `start..end` of the source line.
+    Text,
+    /// A forced line break inserted by the transform (e.g. soft-wrap).
+    Break,
+    /// An indent of `width` columns inserted before the next token.
+    Indent { width: u16 },
+}
+
+/// A single token in the visible-range buffer handed to (and returned from)
+/// an extension's view transform.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewToken {
+    pub kind: ViewTokenKind,
+    /// Byte offset range into the buffer's visible line range, or `0..0`
+    /// for synthetic tokens (e.g. an inserted `Break`) that don't
+    /// correspond to source text.
+    pub start: usize,
+    pub end: usize,
+}
+
+impl ViewToken {
+    pub fn text(start: usize, end: usize) -> Self {
+        Self {
+            kind: ViewTokenKind::Text,
+            start,
+            end,
+        }
+    }
+
+    pub fn break_token() -> Self {
+        Self {
+            kind: ViewTokenKind::Break,
+            start: 0,
+            end: 0,
+        }
+    }
+
+    pub fn indent(width: u16) -> Self {
+        Self {
+            kind: ViewTokenKind::Indent { width },
+            start: 0,
+            end: 0,
+        }
+    }
+}
+
+/// The request payload sent to a guest: the visible line range plus the raw
+/// token buffer for that range.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViewTransformRequest {
+    pub visible_range: std::ops::Range<usize>,
+    pub tokens: Vec<ViewToken>,
+}
+
+/// The guest's response: the edited token stream that replaces `tokens`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViewTransformResponse {
+    pub tokens: Vec<ViewToken>,
+}
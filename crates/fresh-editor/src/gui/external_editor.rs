@@ -0,0 +1,201 @@
+//! "Open in $EDITOR" escape hatch: suspend and hand the current file off to
+//! whatever external program the user already prefers, at the same cursor
+//! position `parse_file_location` would parse back out of its own output.
+//!
+//! The command this builds follows each program's own `file:line:col`-style
+//! argument convention where one is known; otherwise it falls back to a
+//! plain path, since not every `$EDITOR`/`$VISUAL` understands a `file:line`
+//! suffix. Pausing the TUI and waiting for the child belongs to the
+//! terminal/render loop, and there's no `main.rs`/render loop anywhere in
+//! this checkout for that half to live in. What the loop would do once the
+//! child exits — decide whether the reload is clean or needs a merge
+//! prompt — doesn't need a live terminal, so [`ReloadDecision::resolve`]
+//! implements that decision here, ready for the loop to call once it exists,
+//! alongside [`build_command`] and [`resolve_editor_program`].
+
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+/// What to do with a buffer after its external-editor child process exits,
+/// given the buffer's in-app text at launch time, its current (possibly
+/// further-edited) in-app text, and what's now on disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReloadDecision {
+    /// Disk is unchanged from what was on it at launch: nothing to do,
+    /// whether or not the buffer has unsaved in-app edits.
+    NoChange,
+    /// Disk changed and the buffer has no conflicting in-app edits: reload
+    /// the buffer from disk with no prompt.
+    ReloadCleanly { disk_text: String },
+    /// Disk changed *and* the buffer was also edited in-app since launch:
+    /// both sides have content the user might want to keep, so the caller
+    /// should prompt to merge rather than silently pick one.
+    PromptToMerge { disk_text: String },
+}
+
+impl ReloadDecision {
+    /// Decide how to reconcile an external editor's changes with the
+    /// buffer's in-app state. `text_at_launch` is what the buffer held (and
+    /// presumably what was on disk) when the external editor was spawned;
+    /// `text_now` is the buffer's current in-app text (unchanged unless the
+    /// user kept typing while the external editor ran); `disk_text` is what
+    /// the external editor left on disk.
+    pub fn resolve(text_at_launch: &str, text_now: &str, disk_text: &str) -> ReloadDecision {
+        if disk_text == text_at_launch {
+            return ReloadDecision::NoChange;
+        }
+        if text_now == text_at_launch {
+            ReloadDecision::ReloadCleanly { disk_text: disk_text.to_string() }
+        } else {
+            ReloadDecision::PromptToMerge { disk_text: disk_text.to_string() }
+        }
+    }
+}
+
+/// Resolve the external editor to launch: `$VISUAL`, then `$EDITOR`, then
+/// `None` if neither is set (the caller should surface an error rather than
+/// guess at a default).
+pub fn resolve_editor_program() -> Option<String> {
+    env::var("VISUAL").ok().filter(|v| !v.is_empty()).or_else(|| env::var("EDITOR").ok().filter(|v| !v.is_empty()))
+}
+
+/// Build the [`Command`] that launches `program` on `path`, positioned at
+/// `line`/`col` (both 1-based) if given. Recognized editors that support a
+/// `file:line[:col]` argument get it; vi-family editors get a `+line` flag
+/// before the path (the column is dropped); everything else just gets the
+/// plain path, since passing an unsupported suffix would make the editor
+/// open a literal (and usually nonexistent) file.
+pub fn build_command(program: &str, path: &Path, line: Option<u32>, col: Option<u32>) -> Command {
+    let mut command = Command::new(program);
+
+    let binary_name = Path::new(program)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(program);
+
+    match (binary_name, line) {
+        (bin, Some(line)) if supports_file_line_col_suffix(bin) => {
+            let location = match col {
+                Some(col) => format!("{}:{line}:{col}", path.display()),
+                None => format!("{}:{line}", path.display()),
+            };
+            command.arg(location);
+        }
+        ("vim" | "nvim" | "vi", Some(line)) => {
+            command.arg(format!("+{line}")).arg(path);
+        }
+        _ => {
+            command.arg(path);
+        }
+    }
+
+    command
+}
+
+/// Editors known to accept a trailing `:line[:col]` directly on the path
+/// argument (as opposed to vi-family editors, which use a separate `+N`
+/// flag, or editors that don't support positioning at all).
+fn supports_file_line_col_suffix(binary_name: &str) -> bool {
+    matches!(binary_name, "code" | "code-insiders" | "subl" | "emacsclient" | "hx" | "micro")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn args_of(command: &Command) -> Vec<String> {
+        command.get_args().map(|a| a.to_string_lossy().to_string()).collect()
+    }
+
+    #[test]
+    fn test_build_command_for_line_col_suffix_editor() {
+        let command = build_command("hx", &PathBuf::from("src/main.rs"), Some(42), Some(10));
+        assert_eq!(args_of(&command), vec!["src/main.rs:42:10"]);
+    }
+
+    #[test]
+    fn test_build_command_for_line_only_suffix_editor() {
+        let command = build_command("code", &PathBuf::from("src/main.rs"), Some(42), None);
+        assert_eq!(args_of(&command), vec!["src/main.rs:42"]);
+    }
+
+    #[test]
+    fn test_build_command_for_vim_uses_plus_flag() {
+        let command = build_command("vim", &PathBuf::from("src/main.rs"), Some(42), Some(10));
+        assert_eq!(args_of(&command), vec!["+42", "src/main.rs"]);
+    }
+
+    #[test]
+    fn test_build_command_falls_back_to_plain_path_with_no_line() {
+        let command = build_command("nano", &PathBuf::from("src/main.rs"), None, None);
+        assert_eq!(args_of(&command), vec!["src/main.rs"]);
+    }
+
+    #[test]
+    fn test_build_command_unknown_editor_with_line_ignores_line() {
+        let command = build_command("nano", &PathBuf::from("src/main.rs"), Some(42), None);
+        assert_eq!(args_of(&command), vec!["src/main.rs"]);
+    }
+
+    #[test]
+    fn test_build_command_resolves_full_editor_path_by_basename() {
+        let command = build_command("/usr/bin/hx", &PathBuf::from("src/main.rs"), Some(1), None);
+        assert_eq!(args_of(&command), vec!["src/main.rs:1"]);
+    }
+
+    #[test]
+    fn test_resolve_editor_program_prefers_visual_over_editor() {
+        // SAFETY: tests run single-threaded within this process by default
+        // for env-mutating tests; guarded by restoring both vars after.
+        let prior_visual = env::var("VISUAL").ok();
+        let prior_editor = env::var("EDITOR").ok();
+
+        env::set_var("VISUAL", "my-visual-editor");
+        env::set_var("EDITOR", "my-editor");
+        assert_eq!(resolve_editor_program(), Some("my-visual-editor".to_string()));
+
+        env::remove_var("VISUAL");
+        assert_eq!(resolve_editor_program(), Some("my-editor".to_string()));
+
+        env::remove_var("EDITOR");
+        assert_eq!(resolve_editor_program(), None);
+
+        match prior_visual {
+            Some(v) => env::set_var("VISUAL", v),
+            None => env::remove_var("VISUAL"),
+        }
+        match prior_editor {
+            Some(v) => env::set_var("EDITOR", v),
+            None => env::remove_var("EDITOR"),
+        }
+    }
+
+    #[test]
+    fn test_reload_decision_no_change_when_disk_matches_launch() {
+        let decision = ReloadDecision::resolve("fn main() {}\n", "fn main() {}\n", "fn main() {}\n");
+        assert_eq!(decision, ReloadDecision::NoChange);
+    }
+
+    #[test]
+    fn test_reload_decision_clean_reload_when_only_disk_changed() {
+        let decision = ReloadDecision::resolve("old\n", "old\n", "new from $EDITOR\n");
+        assert_eq!(decision, ReloadDecision::ReloadCleanly { disk_text: "new from $EDITOR\n".to_string() });
+    }
+
+    #[test]
+    fn test_reload_decision_prompts_to_merge_on_conflicting_edits() {
+        let decision = ReloadDecision::resolve("old\n", "old\nedited in-app\n", "new from $EDITOR\n");
+        assert_eq!(decision, ReloadDecision::PromptToMerge { disk_text: "new from $EDITOR\n".to_string() });
+    }
+
+    #[test]
+    fn test_reload_decision_no_change_even_with_in_app_edits_if_disk_untouched() {
+        // The external editor ran but didn't actually change anything on
+        // disk (e.g. opened and quit without saving) - the in-app edits are
+        // the only changes, so there's nothing to reload or merge.
+        let decision = ReloadDecision::resolve("old\n", "old\nedited in-app\n", "old\n");
+        assert_eq!(decision, ReloadDecision::NoChange);
+    }
+}
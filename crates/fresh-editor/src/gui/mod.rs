@@ -0,0 +1,146 @@
+//! GUI backend helpers: file-location parsing for command-line/"open at"
+//! arguments, and the pixel <-> cell coordinate math mouse input and window
+//! resizing need.
+//!
+//! The winit-backed rendering surface itself isn't present in this
+//! checkout; these are the pure, backend-independent pieces it would call
+//! into.
+
+pub mod external_editor;
+
+use std::path::PathBuf;
+
+/// Parse a `path[:line[:col]]` command-line argument into its path, line,
+/// and column (both 1-based, matching the convention editors/compilers use
+/// in their own file-location output). Falls back to treating the whole
+/// input as a plain path when the trailing segments aren't numeric, so a
+/// Windows drive letter's colon (`C:\file.rs`) or a path that merely
+/// contains a colon doesn't get misparsed.
+///
+/// A Windows drive-letter prefix (`C:\` or `C:/`) is split off before the
+/// `:line:col` parsing runs, since otherwise its colon is indistinguishable
+/// from a line-number separator and produces a spurious third segment
+/// (`C:\file.rs:42` would rsplit into `["42", "\file.rs", "C"]`, not
+/// `["42", "C:\file.rs"]`).
+pub fn parse_file_location(input: &str) -> (PathBuf, Option<u32>, Option<u32>) {
+    let drive_prefix_len = windows_drive_prefix_len(input);
+    let (drive_prefix, rest) = input.split_at(drive_prefix_len);
+
+    let parts: Vec<&str> = rest.rsplitn(3, ':').collect();
+    let (path, line, col) = match parts.as_slice() {
+        [col, line, path] if line.parse::<u32>().is_ok() && col.parse::<u32>().is_ok() => {
+            (*path, line.parse().ok(), col.parse().ok())
+        }
+        [line, path] if line.parse::<u32>().is_ok() => (*path, line.parse().ok(), None),
+        _ => (rest, None, None),
+    };
+
+    (PathBuf::from(format!("{drive_prefix}{path}")), line, col)
+}
+
+/// Length of a leading Windows drive-letter prefix (e.g. `2` for `C:` in
+/// `C:\file.rs`), or `0` if `input` doesn't start with one. Only the
+/// `X:` itself is consumed — the path separator that follows stays part of
+/// the remainder so it's still treated as an absolute path.
+fn windows_drive_prefix_len(input: &str) -> usize {
+    let bytes = input.as_bytes();
+    let has_drive_letter = bytes.first().is_some_and(u8::is_ascii_alphabetic) && bytes.get(1) == Some(&b':');
+    let has_separator_after = matches!(bytes.get(2), Some(b'\\') | Some(b'/'));
+    if has_drive_letter && has_separator_after {
+        2
+    } else {
+        0
+    }
+}
+
+/// Which cell a pixel coordinate falls into, given the font's cell size.
+pub fn pixel_to_cell(pixel: (f64, f64), cell_size: (f64, f64)) -> (u32, u32) {
+    let col = (pixel.0 / cell_size.0).max(0.0).floor() as u32;
+    let row = (pixel.1 / cell_size.1).max(0.0).floor() as u32;
+    (col, row)
+}
+
+/// How many whole cell columns/rows fit in a `width x height` pixel
+/// surface at the given cell size.
+pub fn cell_dimensions_to_grid(width: f64, height: f64, cell_size: (f64, f64)) -> (u32, u32) {
+    let cols = (width / cell_size.0).max(0.0).floor() as u32;
+    let rows = (height / cell_size.1).max(0.0).floor() as u32;
+    (cols, rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_file_location_plain_path() {
+        let (path, line, col) = parse_file_location("src/main.rs");
+        assert_eq!(path, PathBuf::from("src/main.rs"));
+        assert_eq!(line, None);
+        assert_eq!(col, None);
+    }
+
+    #[test]
+    fn test_parse_file_location_with_line() {
+        let (path, line, col) = parse_file_location("src/main.rs:42");
+        assert_eq!(path, PathBuf::from("src/main.rs"));
+        assert_eq!(line, Some(42));
+        assert_eq!(col, None);
+    }
+
+    #[test]
+    fn test_parse_file_location_with_line_and_col() {
+        let (path, line, col) = parse_file_location("src/main.rs:42:10");
+        assert_eq!(path, PathBuf::from("src/main.rs"));
+        assert_eq!(line, Some(42));
+        assert_eq!(col, Some(10));
+    }
+
+    #[test]
+    fn test_parse_file_location_non_numeric_suffix() {
+        let (path, line, col) = parse_file_location("foo:bar");
+        assert_eq!(path, PathBuf::from("foo:bar"));
+        assert_eq!(line, None);
+        assert_eq!(col, None);
+    }
+
+    #[test]
+    fn test_parse_file_location_windows_drive() {
+        let (path, line, col) = parse_file_location(r"C:\file.rs:10:5");
+        assert_eq!(path, PathBuf::from(r"C:\file.rs"));
+        assert_eq!(line, Some(10));
+        assert_eq!(col, Some(5));
+    }
+
+    #[test]
+    fn test_parse_file_location_windows_drive_line_only() {
+        let (path, line, col) = parse_file_location(r"C:\Users\test\file.rs:42");
+        assert_eq!(path, PathBuf::from(r"C:\Users\test\file.rs"));
+        assert_eq!(line, Some(42));
+        assert_eq!(col, None);
+    }
+
+    #[test]
+    fn test_parse_file_location_windows_drive_plain_path() {
+        let (path, line, col) = parse_file_location(r"C:\file.rs");
+        assert_eq!(path, PathBuf::from(r"C:\file.rs"));
+        assert_eq!(line, None);
+        assert_eq!(col, None);
+    }
+
+    #[test]
+    fn test_pixel_to_cell_typical_font() {
+        let cell_size = (14.4, 28.8);
+        let (col, row) = pixel_to_cell((150.0, 60.0), cell_size);
+        assert_eq!(col, 10);
+        assert_eq!(row, 2);
+    }
+
+    #[test]
+    fn test_grid_dimensions_for_default_window() {
+        let cell_size = (14.4, 28.8);
+        let (cols, rows) = cell_dimensions_to_grid(1280.0, 800.0, cell_size);
+        assert_eq!(cols, 88);
+        assert_eq!(rows, 27);
+    }
+}
@@ -0,0 +1,309 @@
+//! Locale-aware `NUMBER(...)`/`DATETIME(...)` formatting builtins usable
+//! inside translation values, e.g. `{ NUMBER($size, useGrouping: true) }` or
+//! `{ DATETIME($when, dateStyle: "short") }`.
+//!
+//! These are resolved by [`super::runtime_backend`] before the surrounding
+//! `{$name}` placeholders are substituted. Unknown function names degrade to
+//! emitting the raw argument rather than erroring, so a typo in a message
+//! never breaks translation entirely.
+
+use std::collections::HashMap;
+
+/// A parsed keyword argument value from a builtin call, e.g. the `true` in
+/// `useGrouping: true` or the `"short"` in `dateStyle: "short"`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BuiltinValue {
+    Bool(bool),
+    Number(f64),
+    Str(String),
+}
+
+impl BuiltinValue {
+    fn as_bool(&self) -> Option<bool> {
+        match self {
+            BuiltinValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    fn as_u32(&self) -> Option<u32> {
+        match self {
+            BuiltinValue::Number(n) => Some(*n as u32),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            BuiltinValue::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+/// Dispatch a builtin call by name. Returns `None` for unrecognized names so
+/// the caller can fall back to emitting the raw argument.
+pub fn call_builtin(name: &str, locale: &str, arg: &str, kwargs: &HashMap<String, BuiltinValue>) -> Option<String> {
+    match name {
+        "NUMBER" => arg.trim().parse::<f64>().ok().map(|n| format_number(locale, n, kwargs)),
+        "DATETIME" => Some(format_datetime(locale, arg.trim(), kwargs)),
+        _ => None,
+    }
+}
+
+/// Locale separators for number grouping: `(group_separator, decimal_separator)`.
+fn number_separators(locale: &str) -> (&'static str, &'static str) {
+    match locale {
+        "de" | "es" | "it" | "pt-BR" | "vi" => (".", ","),
+        "fr" | "ru" | "uk" | "cs" => (" ", ","),
+        _ => (",", "."), // en, ja, ko, th, zh-CN and unrecognized locales
+    }
+}
+
+/// Format `n` per locale, honoring `useGrouping` (default `true`) and
+/// `minimumFractionDigits` (default `0`: at least that many fraction
+/// digits, more if `n` has them).
+pub fn format_number(locale: &str, n: f64, kwargs: &HashMap<String, BuiltinValue>) -> String {
+    let use_grouping = kwargs.get("useGrouping").and_then(BuiltinValue::as_bool).unwrap_or(true);
+    let min_fraction_digits = kwargs
+        .get("minimumFractionDigits")
+        .and_then(BuiltinValue::as_u32)
+        .unwrap_or(0);
+    let (group_sep, decimal_sep) = number_separators(locale);
+
+    let negative = n < 0.0;
+    let n = n.abs();
+    let own_fraction_digits = n.to_string().split_once('.').map_or(0, |(_, f)| f.len());
+    let fraction_digits = own_fraction_digits.max(min_fraction_digits as usize);
+    let formatted = format!("{:.*}", fraction_digits, n);
+    let (int_part, frac_part) = match formatted.split_once('.') {
+        Some((i, f)) => (i, Some(f)),
+        None => (formatted.as_str(), None),
+    };
+
+    let int_grouped = if use_grouping {
+        group_digits(int_part, group_sep)
+    } else {
+        int_part.to_string()
+    };
+
+    let mut out = String::new();
+    if negative {
+        out.push('-');
+    }
+    out.push_str(&int_grouped);
+    if let Some(frac) = frac_part {
+        out.push_str(decimal_sep);
+        out.push_str(frac);
+    }
+    out
+}
+
+/// Insert `sep` every three digits from the right, e.g. `"1000000"` ->
+/// `"1,000,000"`.
+fn group_digits(digits: &str, sep: &str) -> String {
+    let bytes = digits.as_bytes();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, b) in bytes.iter().enumerate() {
+        let remaining = bytes.len() - i;
+        if i > 0 && remaining % 3 == 0 {
+            out.push_str(sep);
+        }
+        out.push(*b as char);
+    }
+    out
+}
+
+/// A minimal civil date, enough to render `dateStyle: "short"`.
+struct CivilDate {
+    year: i64,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+}
+
+/// Convert a Unix epoch timestamp (seconds) to a UTC civil date/time, using
+/// Howard Hinnant's `civil_from_days` algorithm (proleptic Gregorian, valid
+/// for the full range we care about: no external date/time dependency
+/// needed for a "short date" formatter).
+fn civil_from_epoch_seconds(epoch_secs: i64) -> CivilDate {
+    let days = epoch_secs.div_euclid(86_400);
+    let secs_of_day = epoch_secs.rem_euclid(86_400);
+    let hour = (secs_of_day / 3600) as u32;
+    let minute = ((secs_of_day % 3600) / 60) as u32;
+
+    let z = days + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = (z - era * 146_097) as u64; // [0, 146096]
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365; // [0, 399]
+    let year = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100); // [0, 365]
+    let mp = (5 * doy + 2) / 153; // [0, 11]
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32; // [1, 31]
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32; // [1, 12]
+    let year = if month <= 2 { year + 1 } else { year };
+
+    CivilDate {
+        year,
+        month,
+        day,
+        hour,
+        minute,
+    }
+}
+
+/// Parse `raw` as either a Unix epoch (seconds) or an ISO-8601
+/// `YYYY-MM-DDTHH:MM...` string into a [`CivilDate`].
+fn parse_datetime_input(raw: &str) -> Option<CivilDate> {
+    if let Ok(epoch) = raw.parse::<i64>() {
+        return Some(civil_from_epoch_seconds(epoch));
+    }
+
+    // Minimal ISO-8601 parse: "YYYY-MM-DD" optionally followed by
+    // "THH:MM...".
+    let (date_part, time_part) = raw.split_once('T').unwrap_or((raw, ""));
+    let mut date_fields = date_part.splitn(3, '-');
+    let year: i64 = date_fields.next()?.parse().ok()?;
+    let month: u32 = date_fields.next()?.parse().ok()?;
+    let day: u32 = date_fields.next()?.parse().ok()?;
+
+    let (hour, minute) = if time_part.is_empty() {
+        (0, 0)
+    } else {
+        let mut time_fields = time_part.splitn(3, ':');
+        let hour: u32 = time_fields.next()?.parse().ok()?;
+        let minute: u32 = time_fields.next().unwrap_or("0").parse().ok()?;
+        (hour, minute)
+    };
+
+    Some(CivilDate {
+        year,
+        month,
+        day,
+        hour,
+        minute,
+    })
+}
+
+/// Format per locale, honoring `dateStyle`/`timeStyle` of `"short"`
+/// (the only style currently implemented; others fall back to `"short"`).
+pub fn format_datetime(locale: &str, raw: &str, kwargs: &HashMap<String, BuiltinValue>) -> String {
+    let Some(civil) = parse_datetime_input(raw) else {
+        return raw.to_string();
+    };
+
+    let want_date = kwargs.contains_key("dateStyle") || !kwargs.contains_key("timeStyle");
+    let want_time = kwargs.contains_key("timeStyle");
+
+    let date_str = if want_date {
+        Some(match locale {
+            "en" | "ja" | "ko" | "zh-CN" => {
+                // Locales that put the year first/last per their own
+                // convention; keep it simple with y/m/d order for CJK and
+                // month/day/year for English.
+                if locale == "en" {
+                    format!("{:02}/{:02}/{}", civil.month, civil.day, civil.year)
+                } else {
+                    format!("{}/{:02}/{:02}", civil.year, civil.month, civil.day)
+                }
+            }
+            "de" | "cs" | "ru" | "uk" => format!("{:02}.{:02}.{}", civil.day, civil.month, civil.year),
+            _ => format!("{:02}/{:02}/{}", civil.day, civil.month, civil.year), // fr, es, it, pt-BR, th, vi
+        })
+    } else {
+        None
+    };
+
+    let time_str = if want_time {
+        Some(format!("{:02}:{:02}", civil.hour, civil.minute))
+    } else {
+        None
+    };
+
+    match (date_str, time_str) {
+        (Some(d), Some(t)) => format!("{d} {t}"),
+        (Some(d), None) => d,
+        (None, Some(t)) => t,
+        (None, None) => raw.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kwargs(pairs: &[(&str, BuiltinValue)]) -> HashMap<String, BuiltinValue> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.clone())).collect()
+    }
+
+    #[test]
+    fn test_format_number_en_grouping() {
+        let out = format_number("en", 1_000_000.0, &kwargs(&[("useGrouping", BuiltinValue::Bool(true))]));
+        assert_eq!(out, "1,000,000");
+    }
+
+    #[test]
+    fn test_format_number_de_uses_dot_grouping_comma_decimal() {
+        let out = format_number("de", 1_000_000.0, &HashMap::new());
+        assert_eq!(out, "1.000.000");
+    }
+
+    #[test]
+    fn test_format_number_ru_uses_space_grouping() {
+        let out = format_number("ru", 1_000_000.0, &HashMap::new());
+        assert_eq!(out, "1 000 000");
+    }
+
+    #[test]
+    fn test_format_number_without_grouping() {
+        let out = format_number("en", 1_000_000.0, &kwargs(&[("useGrouping", BuiltinValue::Bool(false))]));
+        assert_eq!(out, "1000000");
+    }
+
+    #[test]
+    fn test_format_number_minimum_fraction_digits() {
+        let out = format_number(
+            "en",
+            3.5,
+            &kwargs(&[("minimumFractionDigits", BuiltinValue::Number(2.0))]),
+        );
+        assert_eq!(out, "3.50");
+    }
+
+    #[test]
+    fn test_format_number_negative() {
+        assert_eq!(format_number("en", -42.0, &HashMap::new()), "-42");
+    }
+
+    #[test]
+    fn test_format_datetime_epoch_short_date_en() {
+        // 2024-01-02T03:04:00Z
+        let out = format_datetime(
+            "en",
+            "1704164640",
+            &kwargs(&[("dateStyle", BuiltinValue::Str("short".to_string()))]),
+        );
+        assert_eq!(out, "01/02/2024");
+    }
+
+    #[test]
+    fn test_format_datetime_iso_short_date_de() {
+        let out = format_datetime(
+            "de",
+            "2024-01-02",
+            &kwargs(&[("dateStyle", BuiltinValue::Str("short".to_string()))]),
+        );
+        assert_eq!(out, "02.01.2024");
+    }
+
+    #[test]
+    fn test_format_datetime_malformed_input_passthrough() {
+        assert_eq!(format_datetime("en", "not-a-date-zzz", &HashMap::new()), "not-a-date-zzz");
+    }
+
+    #[test]
+    fn test_call_builtin_unknown_name_is_none() {
+        assert!(call_builtin("FROBNICATE", "en", "1", &HashMap::new()).is_none());
+    }
+}
@@ -0,0 +1,16 @@
+//! Internationalization support.
+//!
+//! [`runtime_backend`] implements the [`rust_i18n::Backend`] trait by parsing
+//! JSON translation files at runtime instead of expanding them at compile
+//! time. [`plural`] holds the CLDR plural-rule tables used to select among
+//! Fluent-style message variants embedded in translation values. [`registry`]
+//! layers user-supplied overlay files and fallback-chain negotiation on top
+//! of the embedded locales. [`builtins`] provides the `NUMBER`/`DATETIME`
+//! formatting functions usable inside translation values.
+
+pub mod builtins;
+pub mod plural;
+pub mod registry;
+pub mod runtime_backend;
+
+pub use registry::LocaleRegistry;
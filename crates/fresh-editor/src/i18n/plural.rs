@@ -0,0 +1,172 @@
+//! CLDR plural-rule tables for the locales embedded in [`super::runtime_backend`].
+//!
+//! These rules decide which `[one]`/`[few]`/`[many]`/`[other]` variant a
+//! Fluent-style select expression resolves to for a given cardinal number.
+//! Only the categories that can actually be produced for a locale are ever
+//! returned, e.g. [`cardinal_category`] never returns [`PluralCategory::Few`]
+//! for English.
+
+/// A CLDR plural category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralCategory {
+    /// The Fluent/CLDR variant key spelling, e.g. `"one"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PluralCategory::Zero => "zero",
+            PluralCategory::One => "one",
+            PluralCategory::Two => "two",
+            PluralCategory::Few => "few",
+            PluralCategory::Many => "many",
+            PluralCategory::Other => "other",
+        }
+    }
+
+    /// Parse a variant key back into a category, e.g. `"few"` -> `Few`.
+    /// Returns `None` for keys that aren't CLDR categories (explicit numeric
+    /// variants like `[0]` are handled separately by the caller).
+    pub fn parse(key: &str) -> Option<Self> {
+        match key {
+            "zero" => Some(PluralCategory::Zero),
+            "one" => Some(PluralCategory::One),
+            "two" => Some(PluralCategory::Two),
+            "few" => Some(PluralCategory::Few),
+            "many" => Some(PluralCategory::Many),
+            "other" => Some(PluralCategory::Other),
+            _ => None,
+        }
+    }
+}
+
+/// Resolve the CLDR cardinal plural category for `n` in `locale`.
+///
+/// `locale` is matched against the 14 locales embedded in
+/// `EMBEDDED_LOCALES`; an unrecognized locale falls back to the English
+/// rule.
+pub fn cardinal_category(locale: &str, n: f64) -> PluralCategory {
+    match locale {
+        "ru" | "uk" => slavic_category(n),
+        "cs" => czech_category(n),
+        "ja" | "ko" | "zh-CN" | "th" | "vi" => PluralCategory::Other,
+        _ => english_category(n),
+    }
+}
+
+/// English-style rule: singular only for exactly one, used as the default
+/// for locales (en, de, es, fr, it, pt-BR) not called out with a distinct
+/// rule above.
+fn english_category(n: f64) -> PluralCategory {
+    if n == 1.0 {
+        PluralCategory::One
+    } else {
+        PluralCategory::Other
+    }
+}
+
+/// Russian/Ukrainian rule (CLDR): one/few/many/other based on n%10 and
+/// n%100, with fractional values always falling to `other`.
+fn slavic_category(n: f64) -> PluralCategory {
+    if n.fract() != 0.0 || n < 0.0 {
+        return PluralCategory::Other;
+    }
+    let n = n as u64;
+    let mod10 = n % 10;
+    let mod100 = n % 100;
+
+    if mod10 == 1 && mod100 != 11 {
+        PluralCategory::One
+    } else if (2..=4).contains(&mod10) && !(12..=14).contains(&mod100) {
+        PluralCategory::Few
+    } else if mod10 == 0 || (5..=9).contains(&mod10) || (11..=14).contains(&mod100) {
+        PluralCategory::Many
+    } else {
+        PluralCategory::Other
+    }
+}
+
+/// Czech rule (CLDR): `one` for 1, `few` for 2..=4, `many` for non-integers,
+/// `other` otherwise.
+fn czech_category(n: f64) -> PluralCategory {
+    if n.fract() != 0.0 {
+        return PluralCategory::Many;
+    }
+    let n = n as i64;
+    if n == 1 {
+        PluralCategory::One
+    } else if (2..=4).contains(&n) {
+        PluralCategory::Few
+    } else {
+        PluralCategory::Other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english_singular_only_for_one() {
+        assert_eq!(cardinal_category("en", 1.0), PluralCategory::One);
+        assert_eq!(cardinal_category("en", 0.0), PluralCategory::Other);
+        assert_eq!(cardinal_category("en", 2.0), PluralCategory::Other);
+        assert_eq!(cardinal_category("en", 1000000.0), PluralCategory::Other);
+    }
+
+    #[test]
+    fn russian_one_few_many() {
+        assert_eq!(cardinal_category("ru", 1.0), PluralCategory::One);
+        assert_eq!(cardinal_category("ru", 21.0), PluralCategory::One);
+        assert_eq!(cardinal_category("ru", 2.0), PluralCategory::Few);
+        assert_eq!(cardinal_category("ru", 3.0), PluralCategory::Few);
+        assert_eq!(cardinal_category("ru", 5.0), PluralCategory::Many);
+        assert_eq!(cardinal_category("ru", 11.0), PluralCategory::Many);
+        assert_eq!(cardinal_category("ru", 12.0), PluralCategory::Many);
+        assert_eq!(cardinal_category("ru", 0.0), PluralCategory::Many);
+    }
+
+    #[test]
+    fn ukrainian_follows_russian_rule() {
+        assert_eq!(cardinal_category("uk", 1.0), cardinal_category("ru", 1.0));
+        assert_eq!(cardinal_category("uk", 14.0), cardinal_category("ru", 14.0));
+    }
+
+    #[test]
+    fn czech_one_few_other() {
+        assert_eq!(cardinal_category("cs", 1.0), PluralCategory::One);
+        assert_eq!(cardinal_category("cs", 2.0), PluralCategory::Few);
+        assert_eq!(cardinal_category("cs", 4.0), PluralCategory::Few);
+        assert_eq!(cardinal_category("cs", 5.0), PluralCategory::Other);
+        assert_eq!(cardinal_category("cs", 1.5), PluralCategory::Many);
+    }
+
+    #[test]
+    fn cjk_and_thai_vietnamese_always_other() {
+        for locale in ["ja", "ko", "zh-CN", "th", "vi"] {
+            assert_eq!(cardinal_category(locale, 1.0), PluralCategory::Other);
+            assert_eq!(cardinal_category(locale, 2.0), PluralCategory::Other);
+        }
+    }
+
+    #[test]
+    fn category_parse_round_trips() {
+        for cat in [
+            PluralCategory::Zero,
+            PluralCategory::One,
+            PluralCategory::Two,
+            PluralCategory::Few,
+            PluralCategory::Many,
+            PluralCategory::Other,
+        ] {
+            assert_eq!(PluralCategory::parse(cat.as_str()), Some(cat));
+        }
+        assert_eq!(PluralCategory::parse("nonsense"), None);
+    }
+}
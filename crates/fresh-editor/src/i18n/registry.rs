@@ -0,0 +1,256 @@
+//! Runtime locale registry: user-supplied overlay files on top of the
+//! embedded locales, resolved through a negotiated fallback chain.
+//!
+//! Unlike [`super::runtime_backend::RuntimeBackend`], which only knows
+//! about the 14 locales baked into the binary, [`LocaleRegistry`] lets a
+//! deployment register extra translation files (or override individual
+//! strings) at runtime, and resolves lookups through a language/region
+//! fallback chain (`pt-BR -> pt -> en`) instead of the single configured
+//! `fallback` locale.
+
+use super::runtime_backend::RuntimeBackend;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// A single registered overlay file: an owned, flattened `key -> value` map
+/// for one locale, plus enough bookkeeping to support hot-reload.
+struct FileSource {
+    path: PathBuf,
+    locale: String,
+    /// Higher priority wins when the same key is present in more than one
+    /// overlay. Assigned in registration order, so later `register_source`
+    /// calls take precedence over earlier ones.
+    priority: u64,
+    entries: HashMap<String, String>,
+}
+
+/// Resolves translations by consulting, in order: registered overlay files,
+/// the embedded locales, then the locale's fallback chain.
+pub struct LocaleRegistry {
+    backend: RuntimeBackend,
+    overlays: RwLock<Vec<FileSource>>,
+    next_priority: RwLock<u64>,
+}
+
+impl LocaleRegistry {
+    pub fn new() -> Self {
+        Self {
+            backend: RuntimeBackend::new(),
+            overlays: RwLock::new(Vec::new()),
+            next_priority: RwLock::new(0),
+        }
+    }
+
+    /// Parse and register (or re-register, for hot-reload) an overlay JSON
+    /// file for `locale`. The file is flattened the same way embedded
+    /// locale files are, skipping `_`-prefixed metadata keys.
+    pub fn register_source(&self, locale: &str, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref().to_path_buf();
+        let text = fs::read_to_string(&path).map_err(|e| format!("failed to read {:?}: {e}", path))?;
+        let value: Value = serde_json::from_str(&text).map_err(|e| format!("invalid JSON in {:?}: {e}", path))?;
+
+        let mut entries = HashMap::new();
+        flatten_owned(&value, String::new(), &mut entries);
+
+        let priority = {
+            let mut next = self.next_priority.write().unwrap();
+            let p = *next;
+            *next += 1;
+            p
+        };
+
+        let mut overlays = self.overlays.write().unwrap();
+        overlays.retain(|s| s.path != path);
+        overlays.push(FileSource {
+            path,
+            locale: locale.to_string(),
+            priority,
+            entries,
+        });
+        Ok(())
+    }
+
+    /// Drop all registered overlays, reverting to the embedded locales only.
+    pub fn clear_sources(&self) {
+        self.overlays.write().unwrap().clear();
+    }
+
+    /// Resolve `key` for `locale`, walking the fallback chain and, at each
+    /// step, preferring overlays (highest priority first) over the embedded
+    /// backend.
+    pub fn translate(&self, locale: &str, key: &str) -> Option<String> {
+        use rust_i18n::Backend;
+
+        for candidate in fallback_chain(locale) {
+            if let Some(value) = self.translate_exact(&candidate, key) {
+                return Some(value);
+            }
+            if let Some(value) = self.backend.translate(&candidate, key) {
+                return Some(value.to_string());
+            }
+        }
+        None
+    }
+
+    /// Look up `key` in the overlays registered for exactly `locale`
+    /// (no fallback), highest-priority overlay first.
+    fn translate_exact(&self, locale: &str, key: &str) -> Option<String> {
+        let overlays = self.overlays.read().unwrap();
+        // Multiple overlays can target the same locale; the most recently
+        // registered one wins per-key, falling through to older overlays
+        // for keys it doesn't define.
+        let mut by_priority: Vec<&FileSource> =
+            overlays.iter().filter(|s| s.locale == locale).collect();
+        by_priority.sort_by_key(|s| std::cmp::Reverse(s.priority));
+        by_priority.iter().find_map(|s| s.entries.get(key).cloned())
+    }
+
+    /// Union of embedded locales and locales discovered via registered
+    /// overlay files.
+    pub fn available_locales(&self) -> Vec<String> {
+        use rust_i18n::Backend;
+        let mut locales: Vec<String> =
+            self.backend.available_locales().into_iter().map(String::from).collect();
+
+        for source in self.overlays.read().unwrap().iter() {
+            if !locales.iter().any(|l| l == &source.locale) {
+                locales.push(source.locale.clone());
+            }
+        }
+        locales.sort();
+        locales.dedup();
+        locales
+    }
+}
+
+impl Default for LocaleRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Compute the negotiated fallback chain for `locale`, e.g.
+/// `pt-BR -> [pt-BR, pt, en]`, `zh-CN -> [zh-CN, zh, en]`, `en -> [en]`.
+fn fallback_chain(locale: &str) -> Vec<String> {
+    let mut chain = vec![locale.to_string()];
+    if let Some((base, _region)) = locale.split_once('-') {
+        if base != locale {
+            chain.push(base.to_string());
+        }
+    }
+    if !chain.iter().any(|l| l == "en") {
+        chain.push("en".to_string());
+    }
+    chain
+}
+
+/// Like `runtime_backend::flatten_json`, but produces owned `String`s
+/// instead of leaking, since overlay entries must be reloadable.
+fn flatten_owned(value: &Value, prefix: String, output: &mut HashMap<String, String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, val) in map {
+                if key.starts_with('_') {
+                    continue;
+                }
+                let new_prefix = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten_owned(val, new_prefix, output);
+            }
+        }
+        Value::String(s) => {
+            output.insert(prefix, s.clone());
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fallback_chain_region_locale() {
+        assert_eq!(fallback_chain("pt-BR"), vec!["pt-BR", "pt", "en"]);
+        assert_eq!(fallback_chain("zh-CN"), vec!["zh-CN", "zh", "en"]);
+    }
+
+    #[test]
+    fn test_fallback_chain_plain_locale() {
+        assert_eq!(fallback_chain("en"), vec!["en"]);
+        assert_eq!(fallback_chain("de"), vec!["de", "en"]);
+    }
+
+    #[test]
+    fn test_overlay_overrides_embedded() {
+        let registry = LocaleRegistry::new();
+        let dir = tempdir();
+        let path = dir.join("en.json");
+        fs::write(&path, r#"{"action": {"copy": "Copy (custom)"}}"#).unwrap();
+        registry.register_source("en", &path).unwrap();
+
+        assert_eq!(
+            registry.translate("en", "action.copy"),
+            Some("Copy (custom)".to_string())
+        );
+        // Untouched keys still resolve to the embedded translation.
+        assert!(registry.translate("en", "action.paste").is_some());
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_overlay_falls_back_through_chain() {
+        let registry = LocaleRegistry::new();
+        let dir = tempdir();
+        let path = dir.join("pt.json");
+        fs::write(&path, r#"{"greeting": "Ola"}"#).unwrap();
+        registry.register_source("pt", &path).unwrap();
+
+        // pt-BR has no overlay of its own, but should fall back to "pt".
+        assert_eq!(registry.translate("pt-BR", "greeting"), Some("Ola".to_string()));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_reregistering_same_path_replaces_entries() {
+        let registry = LocaleRegistry::new();
+        let dir = tempdir();
+        let path = dir.join("en.json");
+        fs::write(&path, r#"{"greeting": "Hi"}"#).unwrap();
+        registry.register_source("en", &path).unwrap();
+        assert_eq!(registry.translate("en", "greeting"), Some("Hi".to_string()));
+
+        fs::write(&path, r#"{"greeting": "Hello"}"#).unwrap();
+        registry.register_source("en", &path).unwrap();
+        assert_eq!(registry.translate("en", "greeting"), Some("Hello".to_string()));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_available_locales_includes_overlay_only_locale() {
+        let registry = LocaleRegistry::new();
+        let dir = tempdir();
+        let path = dir.join("xx.json");
+        fs::write(&path, r#"{"greeting": "Xx"}"#).unwrap();
+        registry.register_source("xx", &path).unwrap();
+
+        assert!(registry.available_locales().iter().any(|l| l == "xx"));
+        assert!(registry.available_locales().iter().any(|l| l == "en"));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    fn tempdir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "fresh_i18n_registry_test_{:?}",
+            std::thread::current().id()
+        ));
+        let _ = fs::create_dir_all(&dir);
+        dir
+    }
+}
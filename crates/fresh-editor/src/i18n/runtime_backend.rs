@@ -3,6 +3,7 @@
 //! This backend replaces the compile-time macro expansion with runtime JSON parsing,
 //! significantly reducing compiler memory usage while maintaining the same functionality.
 
+use crate::i18n::plural::{cardinal_category, PluralCategory};
 use once_cell::sync::Lazy;
 use serde_json::Value;
 use std::collections::HashMap;
@@ -157,11 +158,437 @@ impl Default for RuntimeBackend {
     }
 }
 
+/// A single `[key] text` arm of a select/plural expression.
+#[derive(Debug, PartialEq, Eq)]
+enum VariantKey {
+    /// An explicit numeric match, e.g. `[0]`.
+    Exact(String),
+    /// A CLDR plural category, e.g. `[few]`.
+    Category(PluralCategory),
+}
+
+#[derive(Debug)]
+struct Variant<'a> {
+    key: VariantKey,
+    is_default: bool,
+    text: &'a str,
+}
+
+/// A parsed `{ $var -> [key] text ... *[other] text }` selector expression,
+/// plus the literal text that surrounded it in the source message.
+struct Selector<'a> {
+    var: &'a str,
+    variants: Vec<Variant<'a>>,
+    prefix: &'a str,
+    suffix: &'a str,
+}
+
+impl<'a> Selector<'a> {
+    /// Pick the variant for `value` (the raw string the caller passed for
+    /// `var`), falling back to the `*`-marked default.
+    fn select(&self, locale: &str, value: &str) -> &'a str {
+        if let Ok(n) = value.trim().parse::<f64>() {
+            let normalized = if n.fract() == 0.0 {
+                format!("{}", n as i64)
+            } else {
+                value.trim().to_string()
+            };
+            if let Some(v) = self.variants.iter().find(|v| match &v.key {
+                VariantKey::Exact(k) => *k == normalized || *k == value.trim(),
+                VariantKey::Category(_) => false,
+            }) {
+                return v.text;
+            }
+
+            let category = cardinal_category(locale, n);
+            if let Some(v) = self.variants.iter().find(|v| match &v.key {
+                VariantKey::Category(c) => *c == category,
+                VariantKey::Exact(_) => false,
+            }) {
+                return v.text;
+            }
+        }
+
+        self.variants
+            .iter()
+            .find(|v| v.is_default)
+            .or_else(|| self.variants.last())
+            .map(|v| v.text)
+            .unwrap_or("")
+    }
+}
+
+/// Parse a `{ $var -> [key] text *[default] text }` block out of `template`,
+/// if it contains one. Returns `None` (fast path) for plain strings.
+fn parse_selector(template: &str) -> Option<Selector<'_>> {
+    let mut search_from = 0;
+    loop {
+        let brace_rel = template[search_from..].find('{')?;
+        let brace = search_from + brace_rel;
+        if let Some(selector) = parse_selector_at(template, brace) {
+            return Some(selector);
+        }
+        search_from = brace + 1;
+    }
+}
+
+/// Try to parse a selector header at the `{` found at byte offset `brace`
+/// in `template`, returning `None` (rather than failing the whole parse)
+/// if it turns out not to be one. Real headers are written with a space
+/// after the brace (`{ $count -> ... }`), but a `{$count}` interpolation
+/// placeholder inside a variant's own text is also a `{` immediately
+/// followed by a `$name` — the `->` check below is what tells the two
+/// apart, so [`parse_selector`] can keep scanning past a placeholder brace
+/// that happens to come before the real header.
+fn parse_selector_at(template: &str, brace: usize) -> Option<Selector<'_>> {
+    let after_open = &template[brace + 1..];
+    let leading_ws = after_open.len() - after_open.trim_start().len();
+    let dollar = after_open[leading_ws..].strip_prefix('$')?;
+    let var_end_rel = dollar.find(|c: char| !(c.is_alphanumeric() || c == '_'))?;
+    let var = &dollar[..var_end_rel];
+    if var.is_empty() {
+        return None;
+    }
+
+    let after_var = &dollar[var_end_rel..];
+    let arrow_rel = after_var.find("->")?;
+    // Anything but whitespace between the var name and `->` means this
+    // isn't actually a selector header.
+    if !after_var[..arrow_rel].trim().is_empty() {
+        return None;
+    }
+    let body_start = brace + 1 + leading_ws + 1 + var_end_rel + arrow_rel + "->".len();
+
+    // Find the matching closing brace for `{`, tracking nesting so
+    // `{$count}` placeholders inside variant text don't terminate the
+    // block early.
+    let mut depth = 1usize;
+    let mut end = None;
+    for (i, c) in template[brace + 1..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    end = Some(brace + 1 + i);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let end = end?;
+
+    let body = &template[body_start..end];
+    let variants = parse_variants(body)?;
+    if variants.is_empty() {
+        return None;
+    }
+
+    Some(Selector {
+        var,
+        variants,
+        prefix: &template[..brace],
+        suffix: &template[end + 1..],
+    })
+}
+
+/// Parse the `[key] text [key2] text2 ...` variant list inside a selector
+/// body (the part after `->`).
+fn parse_variants(body: &str) -> Option<Vec<Variant<'_>>> {
+    let mut variants = Vec::new();
+    let mut rest = body;
+
+    loop {
+        let trimmed = rest.trim_start();
+        if trimmed.is_empty() {
+            break;
+        }
+        rest = trimmed;
+
+        let is_default = rest.starts_with('*');
+        if is_default {
+            rest = &rest[1..];
+        }
+        if !rest.starts_with('[') {
+            // Malformed selector body; bail out to the plain-text path
+            // rather than guessing.
+            return None;
+        }
+        let close = rest.find(']')?;
+        let key_str = rest[1..close].trim();
+        let key = match key_str.parse::<i64>() {
+            Ok(n) => VariantKey::Exact(n.to_string()),
+            Err(_) => VariantKey::Category(PluralCategory::parse(key_str)?),
+        };
+        rest = &rest[close + 1..];
+
+        // The variant's text runs until the next `[`/`*[` marker (or the
+        // end of the body), tracking brace depth so a nested `{$var}`
+        // placeholder's own braces aren't mistaken for the end.
+        let mut text_end = rest.len();
+        let mut depth = 0i32;
+        for (i, c) in rest.char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                '[' if depth == 0 => {
+                    text_end = i;
+                    break;
+                }
+                '*' if depth == 0 && rest[i..].starts_with("*[") => {
+                    text_end = i;
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let text = rest[..text_end].trim();
+        rest = &rest[text_end..];
+
+        variants.push(Variant {
+            key,
+            is_default,
+            text,
+        });
+    }
+
+    Some(variants)
+}
+
+/// Substitute `{$name}` placeholders and `{ NAME($arg, key: value, ...) }`
+/// builtin calls in `text` with values from `args`. A placeholder whose
+/// variable is not in `args` is left untouched; an unknown builtin (or one
+/// that rejects its argument) emits the raw argument value.
+fn interpolate(text: &str, args: &[(&str, &str)], locale: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after_open = &rest[start + 1..];
+        match after_open.find('}') {
+            Some(rel_end) => {
+                let inner = after_open[..rel_end].trim();
+                match resolve_placeholder(inner, args, locale) {
+                    Some(value) => out.push_str(&value),
+                    None => {
+                        out.push('{');
+                        out.push_str(inner);
+                        out.push('}');
+                    }
+                }
+                rest = &after_open[rel_end + 1..];
+            }
+            None => {
+                out.push_str(&rest[start..]);
+                rest = "";
+                break;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Resolve a single `{...}` placeholder body: either a bare `$name`
+/// variable reference, or a `NAME($arg, key: value, ...)` builtin call.
+fn resolve_placeholder(inner: &str, args: &[(&str, &str)], locale: &str) -> Option<String> {
+    if let Some(var) = inner.strip_prefix('$') {
+        return args.iter().find(|(k, _)| *k == var.trim()).map(|(_, v)| v.to_string());
+    }
+
+    let paren = inner.find('(')?;
+    if !inner.ends_with(')') {
+        return None;
+    }
+    let name = inner[..paren].trim();
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let (var_name, kwargs) = parse_call_args(&inner[paren + 1..inner.len() - 1])?;
+    let arg_value = args.iter().find(|(k, _)| *k == var_name)?.1;
+    crate::i18n::builtins::call_builtin(name, locale, arg_value, &kwargs)
+        .or_else(|| Some(arg_value.to_string()))
+}
+
+/// Parse the inside of a builtin call's parens: a required leading `$var`
+/// followed by zero or more `key: value` pairs, where `value` is a quoted
+/// string, `true`/`false`, or a bare number.
+fn parse_call_args(args_str: &str) -> Option<(&str, HashMap<String, crate::i18n::builtins::BuiltinValue>)> {
+    use crate::i18n::builtins::BuiltinValue;
+
+    let mut parts = args_str.split(',').map(str::trim).filter(|s| !s.is_empty());
+    let var_name = parts.next()?.strip_prefix('$')?.trim();
+
+    let mut kwargs = HashMap::new();
+    for part in parts {
+        let (key, value) = part.split_once(':')?;
+        let key = key.trim().to_string();
+        let value = value.trim();
+        let parsed = if let Some(s) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+            BuiltinValue::Str(s.to_string())
+        } else if value == "true" {
+            BuiltinValue::Bool(true)
+        } else if value == "false" {
+            BuiltinValue::Bool(false)
+        } else if let Ok(n) = value.parse::<f64>() {
+            BuiltinValue::Number(n)
+        } else {
+            BuiltinValue::Str(value.to_string())
+        };
+        kwargs.insert(key, parsed);
+    }
+
+    Some((var_name, kwargs))
+}
+
+impl RuntimeBackend {
+    /// Translate `key` in `locale` like [`rust_i18n::Backend::translate`],
+    /// but also evaluate an embedded Fluent-style select/plural expression
+    /// (e.g. `{ $count -> [0] no items [one] {$count} item *[other] {$count}
+    /// items }`) using `args`, and substitute any remaining `{$name}`
+    /// placeholders from the same `args`.
+    ///
+    /// Strings without a selector block are returned unchanged (aside from
+    /// placeholder substitution), so this stays on the same fast path as
+    /// plain `translate` for the vast majority of keys.
+    pub fn translate_resolved(&self, locale: &str, key: &str, args: &[(&str, &str)]) -> Option<String> {
+        use rust_i18n::Backend;
+        let raw = self.translate(locale, key)?;
+
+        match parse_selector(raw) {
+            Some(selector) => {
+                let value = args
+                    .iter()
+                    .find(|(k, _)| *k == selector.var)
+                    .map(|(_, v)| *v)
+                    .unwrap_or("");
+                let chosen = selector.select(locale, value);
+                let mut out = String::new();
+                out.push_str(&interpolate(selector.prefix, args, locale));
+                out.push_str(&interpolate(chosen, args, locale));
+                out.push_str(&interpolate(selector.suffix, args, locale));
+                Some(out)
+            }
+            None => Some(interpolate(raw, args, locale)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use rust_i18n::Backend;
 
+    #[test]
+    fn test_selector_numeric_exact_match() {
+        let template = "{ $count -> [0] no items [one] {$count} item *[other] {$count} items }";
+        let selector = parse_selector(template).expect("should parse");
+        assert_eq!(selector.select("en", "0"), "no items");
+    }
+
+    #[test]
+    fn test_selector_plural_category_match() {
+        let template = "{ $count -> [0] no items [one] {$count} item *[other] {$count} items }";
+        let selector = parse_selector(template).expect("should parse");
+        assert_eq!(selector.select("en", "1"), "{$count} item");
+        assert_eq!(selector.select("en", "5"), "{$count} items");
+    }
+
+    #[test]
+    fn test_selector_falls_back_to_default() {
+        let template = "{ $count -> [one] {$count} item *[other] {$count} items }";
+        let selector = parse_selector(template).expect("should parse");
+        // Non-numeric values can't match a plural category either, so the
+        // `*`-marked default wins.
+        assert_eq!(selector.select("en", "not a number"), "{$count} items");
+    }
+
+    #[test]
+    fn test_selector_uses_locale_specific_category() {
+        let template =
+            "{ $count -> [one] {$count} item [few] {$count} items_few *[other] {$count} items }";
+        let selector = parse_selector(template).expect("should parse");
+        assert_eq!(selector.select("ru", "3"), "{$count} items_few");
+        assert_eq!(selector.select("en", "3"), "{$count} items");
+    }
+
+    #[test]
+    fn test_selector_header_with_no_space_after_brace_still_parses() {
+        // `{$count -> ...}` (no space after the brace) is also valid; the
+        // header-vs-placeholder check is the `->`, not the whitespace.
+        let template = "{$count -> [0] no items *[other] {$count} items}";
+        let selector = parse_selector(template).expect("should parse");
+        assert_eq!(selector.select("en", "0"), "no items");
+    }
+
+    #[test]
+    fn test_selector_not_confused_by_earlier_placeholder_brace() {
+        // A `{$other}` placeholder with no `->` that happens to appear
+        // lexically before the real ` { $count -> ... }` header (e.g. once
+        // interpolated into a prefix) must not be mistaken for the header.
+        let template = "prefix {$other} { $count -> [one] item *[other] items }";
+        let selector = parse_selector(template).expect("should parse");
+        assert_eq!(selector.var, "count");
+        assert_eq!(selector.prefix, "prefix {$other} ");
+        assert_eq!(selector.select("en", "1"), "item");
+    }
+
+    #[test]
+    fn test_plain_placeholder_only_string_is_not_a_selector() {
+        assert!(parse_selector("{$count} items").is_none());
+    }
+
+    #[test]
+    fn test_interpolate_substitutes_known_and_skips_unknown() {
+        let out = interpolate(
+            "{$count} of {$total}, {$missing}",
+            &[("count", "2"), ("total", "5")],
+            "en",
+        );
+        assert_eq!(out, "2 of 5, {$missing}");
+    }
+
+    #[test]
+    fn test_interpolate_resolves_number_builtin() {
+        let out = interpolate(
+            "Size: { NUMBER($size, useGrouping: true) }",
+            &[("size", "1000000")],
+            "en",
+        );
+        assert_eq!(out, "Size: 1,000,000");
+    }
+
+    #[test]
+    fn test_interpolate_resolves_datetime_builtin() {
+        let out = interpolate(
+            "On { DATETIME($when, dateStyle: \"short\") }",
+            &[("when", "2024-01-02")],
+            "de",
+        );
+        assert_eq!(out, "On 02.01.2024");
+    }
+
+    #[test]
+    fn test_interpolate_unknown_builtin_emits_raw_argument() {
+        let out = interpolate("{ FROBNICATE($x) }", &[("x", "1")], "en");
+        assert_eq!(out, "1");
+    }
+
+    #[test]
+    fn test_translate_resolved_plain_string_unaffected() {
+        let backend = RuntimeBackend::new();
+        let resolved = backend.translate_resolved("en", "action.copy", &[]);
+        assert_eq!(resolved, backend.translate("en", "action.copy").map(str::to_string));
+    }
+
+    #[test]
+    fn test_translate_resolved_missing_key_is_none() {
+        let backend = RuntimeBackend::new();
+        assert!(backend.translate_resolved("en", "nonexistent.key", &[]).is_none());
+    }
+
     #[test]
     fn test_parse_all_locales() {
         for (locale, json) in EMBEDDED_LOCALES {
@@ -34,8 +34,14 @@ pub mod primitives;
 #[cfg(feature = "runtime")]
 pub mod app;
 #[cfg(feature = "runtime")]
+pub mod extensions;
+#[cfg(feature = "gui")]
+pub mod gui;
+#[cfg(feature = "runtime")]
 pub mod input;
 #[cfg(feature = "runtime")]
+pub mod save;
+#[cfg(feature = "runtime")]
 pub mod services;
 
 // Session persistence (client-server architecture)
@@ -0,0 +1,166 @@
+//! Atomic, crash-safe buffer writes for
+//! [`save::auto_save_persistent_buffers`](crate::save::auto_save_persistent_buffers)
+//! and [`save::auto_recovery_save_dirty_buffers`](crate::save::auto_recovery_save_dirty_buffers),
+//! via [`save::write_buffer`](crate::save::write_buffer), which both call.
+//!
+//! Writing straight to the target path truncates it first, so a crash or
+//! power loss mid-write can leave a half-written or empty file. Instead,
+//! [`write_atomically`] writes to a sibling temp file (copying the
+//! target's permissions onto it first, when the target already exists)
+//! and renames it over the target — on the same filesystem, `rename` is
+//! atomic, so the target is always either fully the old content or fully
+//! the new content, never a partial write. If the directory can't hold a
+//! temp file (e.g. a read-only mount with a writable target file), this
+//! falls back to a direct write.
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Write `contents` to `path` via a temp-file-then-rename, falling back to
+/// a direct write if a sibling temp file can't be created (e.g. a
+/// read-only directory with a writable target). Preserves the target's
+/// existing permissions on the replacement when the target already exists.
+pub fn write_atomically(path: &Path, contents: &[u8]) -> io::Result<()> {
+    write_via_temp_and_rename(path, contents)
+}
+
+fn write_via_temp_and_rename(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let temp_path = sibling_temp_path(path);
+
+    {
+        let mut temp_file = match File::create(&temp_path) {
+            Ok(file) => file,
+            // No sibling temp file possible: write the target directly.
+            Err(_) => return fs::write(path, contents),
+        };
+        temp_file.write_all(contents)?;
+        temp_file.sync_all()?;
+    }
+
+    if let Ok(metadata) = fs::metadata(path) {
+        // Best-effort: a failure here shouldn't abort the save, since the
+        // content itself is still written correctly either way.
+        let _ = fs::set_permissions(&temp_path, metadata.permissions());
+    }
+
+    match fs::rename(&temp_path, path) {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            let _ = fs::remove_file(&temp_path);
+            Err(e)
+        }
+    }
+}
+
+/// A sibling path in the same directory as `path`, so the rename that
+/// commits the write stays on the same filesystem (required for it to be
+/// atomic) — `.fresh-save-<file name>.tmp`.
+fn sibling_temp_path(path: &Path) -> PathBuf {
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("buffer");
+    let temp_name = format!(".fresh-save-{file_name}.tmp");
+    path.with_file_name(temp_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    fn temp_dir() -> PathBuf {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let dir = std::env::temp_dir().join(format!("fresh-atomic-write-test-{}-{n}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_write_atomically_creates_new_file() {
+        let dir = temp_dir();
+        let path = dir.join("new.txt");
+
+        write_atomically(&path, b"hello").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"hello");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_write_atomically_replaces_existing_file() {
+        let dir = temp_dir();
+        let path = dir.join("existing.txt");
+        fs::write(&path, b"old content").unwrap();
+
+        write_atomically(&path, b"new content").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"new content");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_write_atomically_leaves_no_temp_file_behind() {
+        let dir = temp_dir();
+        let path = dir.join("clean.txt");
+
+        write_atomically(&path, b"content").unwrap();
+
+        let leftover = fs::read_dir(&dir)
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .any(|e| e.file_name().to_string_lossy().contains(".tmp"));
+        assert!(!leftover, "temp file should be renamed away, not left behind");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_interrupted_write_never_corrupts_original() {
+        // Simulate "interrupted partway": the temp file is written but the
+        // rename never happens (as if the process died first). The
+        // original file must be fully intact, not truncated or partial.
+        let dir = temp_dir();
+        let path = dir.join("simulated_crash.txt");
+        fs::write(&path, b"original content").unwrap();
+
+        let temp_path = sibling_temp_path(&path);
+        fs::write(&temp_path, b"only half of the new").unwrap();
+        // Note: no rename — this is the "crash before commit" state.
+
+        assert_eq!(
+            fs::read(&path).unwrap(),
+            b"original content",
+            "original must be untouched until the rename commits"
+        );
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_atomically_preserves_existing_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = temp_dir();
+        let path = dir.join("perms.txt");
+        fs::write(&path, b"old").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o640)).unwrap();
+
+        write_atomically(&path, b"new").unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode, 0o640);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_reports_error_when_temp_and_direct_write_both_fail() {
+        // A path whose parent directory doesn't exist can't hold a sibling
+        // temp file, and the direct-write fallback fails the same way (both
+        // go through the same nonexistent directory), so write_atomically
+        // must report the error rather than a successful write.
+        let path = PathBuf::from("/nonexistent-fresh-test-dir/file.txt");
+        assert!(write_atomically(&path, b"x").is_err());
+    }
+}
@@ -0,0 +1,68 @@
+//! Final-newline normalization, intended to be gated by an
+//! `editor.insert_final_newline` config flag (default `true`).
+//! [`save::write_buffer`](crate::save::write_buffer) is the real call
+//! site — it always calls [`ensure_final_newline`] today since there's no
+//! `config.rs`/`Config` anywhere in this checkout yet to read the flag
+//! from; whoever adds it can gate the call there.
+//!
+//! Mirrors the POSIX "text file ends in a newline" convention: if the last
+//! line is non-empty and lacks a trailing line ending, one is appended.
+//! Already-terminated and genuinely empty buffers are returned unchanged,
+//! so repeated saves are idempotent.
+
+use crate::save::LineEnding;
+
+/// Append `ending` to `text` if its last line is non-empty and not already
+/// terminated. Returns an unchanged copy of `text` when nothing needs to
+/// change, so callers can check `is_modified()`-style dirtiness against the
+/// result.
+pub fn ensure_final_newline(text: &str, ending: LineEnding) -> String {
+    if text.is_empty() || ends_with_any_newline(text) {
+        return text.to_string();
+    }
+    format!("{text}{}", ending.resolve_default().as_str())
+}
+
+/// Whether `text` already ends in `\n`, `\r\n`, or a bare `\r`.
+fn ends_with_any_newline(text: &str) -> bool {
+    text.ends_with('\n') || text.ends_with('\r')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_appends_newline_to_unterminated_text() {
+        assert_eq!(ensure_final_newline("hello", LineEnding::Lf), "hello\n");
+    }
+
+    #[test]
+    fn test_leaves_already_terminated_text_untouched() {
+        assert_eq!(ensure_final_newline("hello\n", LineEnding::Lf), "hello\n");
+        assert_eq!(ensure_final_newline("hello\r\n", LineEnding::Crlf), "hello\r\n");
+    }
+
+    #[test]
+    fn test_leaves_empty_text_untouched() {
+        assert_eq!(ensure_final_newline("", LineEnding::Lf), "");
+    }
+
+    #[test]
+    fn test_is_idempotent() {
+        let once = ensure_final_newline("hello", LineEnding::Lf);
+        let twice = ensure_final_newline(&once, LineEnding::Lf);
+        assert_eq!(once, twice);
+    }
+
+    #[test]
+    fn test_uses_requested_ending() {
+        assert_eq!(ensure_final_newline("hello", LineEnding::Crlf), "hello\r\n");
+        assert_eq!(ensure_final_newline("hello", LineEnding::Cr), "hello\r");
+    }
+
+    #[test]
+    fn test_bare_cr_counts_as_terminated() {
+        assert_eq!(ensure_final_newline("hello\r", LineEnding::Lf), "hello\r");
+    }
+}
@@ -0,0 +1,187 @@
+//! Format-on-save: run a per-language external formatter over buffer text
+//! before writing, without ever blocking the save if the formatter
+//! misbehaves.
+//!
+//! The per-language command (e.g. `rustfmt`, `prettier --stdin-filepath`)
+//! would normally come from `Config` (declared in `lib.rs` but with no
+//! `config.rs`/`config/` backing it anywhere in this checkout, so there's
+//! nothing to read it from yet); [`run_formatter`] takes the command
+//! directly so the save path can look it up however it ends up being
+//! configured. [`should_run_formatter`] gates it to explicit saves only —
+//! [`save::write_buffer`](crate::save::write_buffer) is the real call site
+//! that checks it before running [`run_formatter`].
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::thread;
+
+/// Which kind of save is in progress. [`should_run_formatter`] is the
+/// actual decision [`save::write_buffer`](crate::save::write_buffer) uses
+/// to gate [`run_formatter`]: only an explicit save runs it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveKind {
+    /// A user-initiated save (e.g. Ctrl+S).
+    Explicit,
+    /// A periodic background save of a buffer with a known file path.
+    AutoSave,
+    /// A periodic background save to the recovery location, independent
+    /// of whether the buffer has a persistent path yet.
+    RecoverySave,
+}
+
+/// Whether `kind` should run the configured formatter before writing.
+/// Only explicit saves do — auto-save and recovery-save skip it to stay
+/// cheap, since a formatter can be slow and firing it on every periodic
+/// tick would make the feature a liability instead of a convenience.
+pub fn should_run_formatter(kind: SaveKind) -> bool {
+    matches!(kind, SaveKind::Explicit)
+}
+
+/// The result of attempting to format a buffer before save. The save path
+/// always has *something* to write, whichever variant it gets back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatOutcome {
+    /// The formatter ran successfully; `text` is the formatted result and
+    /// should replace the buffer content before writing.
+    Formatted { text: String },
+    /// The formatter failed (non-zero exit, crash, or couldn't be spawned
+    /// at all) or produced empty output where the input wasn't empty;
+    /// `original` is the unformatted text the save must fall back to
+    /// writing, and `message` is a non-fatal status to surface.
+    Failed { original: String, message: String },
+}
+
+/// Run `command args...` with `text` piped to stdin and its stdout captured
+/// as the formatted result. Never returns an error: any failure to spawn,
+/// run, or parse output downgrades to [`FormatOutcome::Failed`] carrying
+/// the original text, so the caller can always proceed to write *something*.
+pub fn run_formatter(text: &str, command: &str, args: &[String]) -> FormatOutcome {
+    let fallback = |message: String| FormatOutcome::Failed {
+        original: text.to_string(),
+        message,
+    };
+
+    let mut child = match Command::new(command)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => return fallback(format!("failed to run formatter '{command}': {e}")),
+    };
+
+    // Written from a separate thread, concurrently with wait_with_output
+    // draining stdout/stderr below: writing stdin to completion first would
+    // deadlock once `text` (or the formatter's own output) exceeds the
+    // OS pipe buffer, since the parent would block writing while the child
+    // blocks on its own undrained stdout.
+    let stdin_writer = child.stdin.take().map(|mut stdin| {
+        let text = text.to_string();
+        thread::spawn(move || stdin.write_all(text.as_bytes()))
+    });
+
+    let output = match child.wait_with_output() {
+        Ok(output) => output,
+        Err(e) => return fallback(format!("formatter '{command}' crashed: {e}")),
+    };
+
+    if let Some(writer) = stdin_writer {
+        match writer.join() {
+            Ok(Err(e)) => return fallback(format!("failed to write to formatter '{command}': {e}")),
+            Err(_) => return fallback(format!("stdin-writing thread for formatter '{command}' panicked")),
+            Ok(Ok(())) => {}
+        }
+    }
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return fallback(format!(
+            "formatter '{command}' exited with {}: {}",
+            output.status,
+            stderr.trim()
+        ));
+    }
+
+    match String::from_utf8(output.stdout) {
+        Ok(formatted) if formatted.is_empty() && !text.is_empty() => {
+            fallback(format!("formatter '{command}' produced empty output for non-empty input"))
+        }
+        Ok(formatted) => FormatOutcome::Formatted { text: formatted },
+        Err(e) => fallback(format!("formatter '{command}' produced non-UTF-8 output: {e}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_successful_formatter_replaces_text() {
+        // `cat` is a stand-in formatter that passes text through unchanged,
+        // which is enough to exercise the success path without depending
+        // on a real formatter being installed.
+        let outcome = run_formatter("hello\n", "cat", &[]);
+        assert_eq!(
+            outcome,
+            FormatOutcome::Formatted {
+                text: "hello\n".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_nonzero_exit_falls_back_to_original() {
+        let outcome = run_formatter("hello\n", "false", &[]);
+        match outcome {
+            FormatOutcome::Failed { original, message } => {
+                assert_eq!(original, "hello\n");
+                assert!(message.contains("false"), "message was: {message}");
+            }
+            FormatOutcome::Formatted { .. } => panic!("expected a failure from `false`"),
+        }
+    }
+
+    #[test]
+    fn test_unspawnable_command_falls_back_to_original() {
+        let outcome = run_formatter("hello\n", "definitely-not-a-real-command-xyz", &[]);
+        match outcome {
+            FormatOutcome::Failed { original, .. } => assert_eq!(original, "hello\n"),
+            FormatOutcome::Formatted { .. } => panic!("expected a failure from a missing binary"),
+        }
+    }
+
+    #[test]
+    fn test_empty_output_for_nonempty_input_falls_back() {
+        // `true` exits 0 but writes nothing to stdout.
+        let outcome = run_formatter("hello\n", "true", &[]);
+        match outcome {
+            FormatOutcome::Failed { original, .. } => assert_eq!(original, "hello\n"),
+            FormatOutcome::Formatted { .. } => panic!("expected empty output to be treated as a failure"),
+        }
+    }
+
+    #[test]
+    fn test_empty_input_with_empty_output_succeeds() {
+        let outcome = run_formatter("", "cat", &[]);
+        assert_eq!(outcome, FormatOutcome::Formatted { text: String::new() });
+    }
+
+    #[test]
+    fn test_should_run_formatter_only_for_explicit_save() {
+        assert!(should_run_formatter(SaveKind::Explicit));
+        assert!(!should_run_formatter(SaveKind::AutoSave));
+        assert!(!should_run_formatter(SaveKind::RecoverySave));
+    }
+
+    #[test]
+    fn test_large_input_does_not_deadlock_on_full_pipe_buffer() {
+        // Bigger than the ~64KB default OS pipe buffer: writing this to
+        // stdin before draining stdout would previously deadlock, since
+        // `cat` echoes it straight back out on a pipe of the same size.
+        let text = "x".repeat(1024 * 1024);
+        let outcome = run_formatter(&text, "cat", &[]);
+        assert_eq!(outcome, FormatOutcome::Formatted { text });
+    }
+}
@@ -0,0 +1,246 @@
+//! Line-ending detection and re-encoding.
+//!
+//! On open, the dominant line ending of a file's content should be detected
+//! via [`LineEnding::detect`] and stored alongside the buffer so every
+//! later write through the save/auto-save/recovery-save paths can
+//! [`LineEnding::encode`] back to it — editing a CRLF file on a Unix host
+//! shouldn't silently rewrite every line to LF. Brand-new buffers with no
+//! detected convention fall back to whatever `editor.default_line_ending`
+//! resolves to (see [`LineEnding::resolve_default`]). [`reencode_for_save`]
+//! is the actual save-time decision combining both: `Buffer`/`Config`
+//! aren't present in this checkout (no `buffer.rs`/`config.rs` exists
+//! anywhere in this crate for `lib.rs`'s declared modules to point at), so
+//! [`save::write_buffer`](crate::save::write_buffer) takes the buffer's
+//! previously-detected ending and the configured default as plain
+//! arguments instead of reading them off those types.
+
+/// A line-ending convention. `Native` is only meaningful as a
+/// `default_line_ending` config value — actual buffer/file content is
+/// always represented as one of the other three once resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    Lf,
+    Crlf,
+    Cr,
+    /// Whatever this host's convention is: CRLF on Windows, LF elsewhere.
+    /// Only valid as a `default_line_ending` setting; resolve it with
+    /// [`LineEnding::resolve_default`] before using it to encode text.
+    Native,
+}
+
+impl LineEnding {
+    /// The literal bytes this ending is written as. Panics on `Native` —
+    /// callers must resolve it first via [`resolve_default`](Self::resolve_default).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n",
+            LineEnding::Cr => "\r",
+            LineEnding::Native => unreachable!("LineEnding::Native must be resolved before use"),
+        }
+    }
+
+    /// Parse an `editor.default_line_ending` config value: `"lf"`,
+    /// `"crlf"`, `"cr"`, or `"native"`.
+    pub fn parse_config_value(value: &str) -> Option<Self> {
+        match value {
+            "lf" => Some(LineEnding::Lf),
+            "crlf" => Some(LineEnding::Crlf),
+            "cr" => Some(LineEnding::Cr),
+            "native" => Some(LineEnding::Native),
+            _ => None,
+        }
+    }
+
+    /// Resolve `Native` to this host's actual convention; any other
+    /// variant is already concrete and is returned as-is.
+    pub fn resolve_default(self) -> Self {
+        match self {
+            LineEnding::Native => {
+                if cfg!(windows) {
+                    LineEnding::Crlf
+                } else {
+                    LineEnding::Lf
+                }
+            }
+            concrete => concrete,
+        }
+    }
+
+    /// Detect the dominant line ending used in `text` by counting each
+    /// kind across the whole buffer rather than just sniffing the first
+    /// line, so a file with a handful of stray endings still normalizes
+    /// the way most of its lines already look. Returns `None` for text with
+    /// no line endings at all (a single line, or empty), leaving the
+    /// decision to [`resolve_default`](Self::resolve_default).
+    pub fn detect(text: &str) -> Option<Self> {
+        let (mut crlf, mut lf, mut cr) = (0u32, 0u32, 0u32);
+        let bytes = text.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\r' if bytes.get(i + 1) == Some(&b'\n') => {
+                    crlf += 1;
+                    i += 2;
+                }
+                b'\r' => {
+                    cr += 1;
+                    i += 1;
+                }
+                b'\n' => {
+                    lf += 1;
+                    i += 1;
+                }
+                _ => i += 1,
+            }
+        }
+
+        [(crlf, LineEnding::Crlf), (lf, LineEnding::Lf), (cr, LineEnding::Cr)]
+            .into_iter()
+            .filter(|(count, _)| *count > 0)
+            .max_by_key(|(count, _)| *count)
+            .map(|(_, ending)| ending)
+    }
+
+    /// Re-encode `text` (assumed already normalized to bare `\n` internally,
+    /// the way most text buffers store lines) to this ending.
+    pub fn encode(self, text: &str) -> String {
+        let resolved = self.resolve_default();
+        if resolved == LineEnding::Lf {
+            return text.to_string();
+        }
+        text.replace('\n', resolved.as_str())
+    }
+
+    /// Strip any line ending in `text` down to bare `\n`, the inverse of
+    /// [`encode`](Self::encode), for loading file content into a buffer's
+    /// internal representation.
+    pub fn normalize_to_lf(text: &str) -> String {
+        text.replace("\r\n", "\n").replace('\r', "\n")
+    }
+
+    /// Convert `text` (currently encoded as `from`) to `to`. Backs an
+    /// explicit "convert line endings" command on the active buffer: the
+    /// command would call this with the buffer's on-disk content and its
+    /// currently-detected ending, then re-save with the stored ending
+    /// updated to `to`.
+    pub fn convert(text: &str, from: LineEnding, to: LineEnding) -> String {
+        let internal = if from.resolve_default() == LineEnding::Lf {
+            text.to_string()
+        } else {
+            Self::normalize_to_lf(text)
+        };
+        to.encode(&internal)
+    }
+}
+
+/// Decide which ending to save `text` (bare `\n` internally, though `\r\n`
+/// pairs already in it are collapsed to `\n` first) as, and re-encode it to
+/// that ending: `known_ending` if the buffer
+/// already has one (from a prior [`LineEnding::detect`] on open), else
+/// re-detecting from `text` itself, else `default` for a buffer with no
+/// convention at all (brand-new or single-line). This is the save-time
+/// decision the module doc comment above describes.
+pub fn reencode_for_save(text: &str, known_ending: Option<LineEnding>, default: LineEnding) -> (String, LineEnding) {
+    let ending = known_ending
+        .or_else(|| LineEnding::detect(text))
+        .unwrap_or(default)
+        .resolve_default();
+    (ending.encode(&text.replace("\r\n", "\n")), ending)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_lf() {
+        assert_eq!(LineEnding::detect("a\nb\nc\n"), Some(LineEnding::Lf));
+    }
+
+    #[test]
+    fn test_detect_crlf() {
+        assert_eq!(LineEnding::detect("a\r\nb\r\nc\r\n"), Some(LineEnding::Crlf));
+    }
+
+    #[test]
+    fn test_detect_bare_cr() {
+        assert_eq!(LineEnding::detect("a\rb\rc\r"), Some(LineEnding::Cr));
+    }
+
+    #[test]
+    fn test_detect_picks_dominant_ending_in_mixed_file() {
+        assert_eq!(LineEnding::detect("a\nb\nc\nd\r\n"), Some(LineEnding::Lf));
+    }
+
+    #[test]
+    fn test_detect_none_for_single_line() {
+        assert_eq!(LineEnding::detect("no newlines here"), None);
+        assert_eq!(LineEnding::detect(""), None);
+    }
+
+    #[test]
+    fn test_encode_round_trips_through_normalize() {
+        let original = "a\r\nb\r\nc\r\n";
+        let internal = LineEnding::normalize_to_lf(original);
+        assert_eq!(internal, "a\nb\nc\n");
+        assert_eq!(LineEnding::Crlf.encode(&internal), original);
+    }
+
+    #[test]
+    fn test_encode_lf_is_a_no_op() {
+        assert_eq!(LineEnding::Lf.encode("a\nb\n"), "a\nb\n");
+    }
+
+    #[test]
+    fn test_parse_config_value() {
+        assert_eq!(LineEnding::parse_config_value("lf"), Some(LineEnding::Lf));
+        assert_eq!(LineEnding::parse_config_value("crlf"), Some(LineEnding::Crlf));
+        assert_eq!(LineEnding::parse_config_value("cr"), Some(LineEnding::Cr));
+        assert_eq!(LineEnding::parse_config_value("native"), Some(LineEnding::Native));
+        assert_eq!(LineEnding::parse_config_value("bogus"), None);
+    }
+
+    #[test]
+    fn test_convert_crlf_to_lf() {
+        assert_eq!(LineEnding::convert("a\r\nb\r\n", LineEnding::Crlf, LineEnding::Lf), "a\nb\n");
+    }
+
+    #[test]
+    fn test_convert_lf_to_crlf() {
+        assert_eq!(LineEnding::convert("a\nb\n", LineEnding::Lf, LineEnding::Crlf), "a\r\nb\r\n");
+    }
+
+    #[test]
+    fn test_resolve_default_is_a_no_op_for_concrete_endings() {
+        assert_eq!(LineEnding::Lf.resolve_default(), LineEnding::Lf);
+        assert_eq!(LineEnding::Crlf.resolve_default(), LineEnding::Crlf);
+        assert_eq!(LineEnding::Cr.resolve_default(), LineEnding::Cr);
+    }
+
+    #[test]
+    fn test_reencode_for_save_prefers_known_ending_over_detection() {
+        // Internal representation is bare \n; the buffer's stored ending
+        // (from when it was opened) is CRLF, so known_ending wins over
+        // re-detecting (which would count only bare \n here and pick LF).
+        let (encoded, ending) = reencode_for_save("a\nb\n", Some(LineEnding::Crlf), LineEnding::Lf);
+        assert_eq!(ending, LineEnding::Crlf);
+        assert_eq!(encoded, "a\r\nb\r\n");
+    }
+
+    #[test]
+    fn test_reencode_for_save_detects_when_no_known_ending() {
+        // No known_ending -- detect() has to work off the (already
+        // re-encoded) text itself.
+        let (encoded, ending) = reencode_for_save("a\r\nb\r\n", None, LineEnding::Lf);
+        assert_eq!(ending, LineEnding::Crlf);
+        assert_eq!(encoded, "a\r\nb\r\n");
+    }
+
+    #[test]
+    fn test_reencode_for_save_falls_back_to_default_for_single_line() {
+        let (encoded, ending) = reencode_for_save("no newlines here", None, LineEnding::Crlf);
+        assert_eq!(ending, LineEnding::Crlf);
+        assert_eq!(encoded, "no newlines here");
+    }
+}
@@ -1,27 +1,31 @@
-//! E2E tests for LSP toggle desync bug (GitHub issue #952)
+//! E2E tests for LSP toggle desync bug (GitHub issue #952) and its fix.
 //!
 //! When LSP is toggled off and back on, the editor must re-send didOpen
-//! with the current buffer content. If it doesn't (because the async handler
-//! skips the didOpen since the document is still tracked in document_versions),
-//! the server's view of the document becomes stale. Subsequent didChange
-//! messages will have invalid ranges relative to the server's stale content,
-//! causing TypeScript Server errors like:
+//! with the current buffer content. The original bug: toggling off never
+//! sent didClose, so document_versions still had the path tracked on
+//! re-enable, should_skip_did_open returned true, and the re-enable didOpen
+//! was skipped entirely. Subsequent didChange messages then had invalid
+//! ranges relative to the server's stale content, causing TypeScript Server
+//! errors like:
 //!   "TypeError: Cannot read properties of undefined (reading 'charCount')"
 //! in the encodedSemanticClassifications-full handler.
 //!
-//! Bug flow:
-//! 1. Open file -> didOpen sent, document_versions[path] = 0
+//! Fixed flow (via `lsp_toggle_for_buffer`'s didClose+didOpen resync path,
+//! also exposed directly as `lsp_stop`/`lsp_restart`):
+//! 1. Open file -> didOpen sent, document_versions[(server, path)] = 0
 //! 2. Edit -> didChange sent, version incremented
-//! 3. Toggle LSP OFF -> lsp_opened_with cleared, but NO didClose sent
+//! 3. Toggle LSP OFF -> didClose sent for every attached server, and the
+//!    (server, path) pair is cleared from document_versions
 //! 4. Edit while LSP disabled -> buffer changes, server not notified
-//! 5. Toggle LSP ON -> tries didOpen, but should_skip_did_open returns true
-//!    because document_versions still has the path. didOpen is SKIPPED.
-//! 6. Edit -> didChange sent with ranges relative to current buffer,
-//!    but server has stale content from step 2. DESYNC!
+//! 5. Toggle LSP ON -> didOpen is sent unconditionally (not gated on
+//!    should_skip_did_open, since step 3 already cleared the tracking) with
+//!    the current buffer content, and the version counter resets to 0
+//! 6. Edit -> didChange sent with ranges relative to the buffer content the
+//!    server was just re-synced to. No desync.
 //!
-//! Reproduction confirmed in tmux with real typescript-language-server 5.1.3
-//! and TypeScript 5.9.3: after toggle off + add text + toggle on + delete
-//! the added text, the TSP crashes with:
+//! Reproduction of the original bug was confirmed in tmux with real
+//! typescript-language-server 5.1.3 and TypeScript 5.9.3: after toggle off +
+//! add text + toggle on + delete the added text, the TSP crashed with:
 //!   "Semantic tokens range request failed: LSP error: <semantic>
 //!    TypeScript Server Error (5.9.3)
 //!    TypeError: Cannot read properties of undefined (reading 'charCount')"
@@ -137,22 +141,21 @@ done
     script_path
 }
 
-/// Test that toggling LSP off, editing, and toggling back on causes a desync
-/// because didOpen is skipped on re-enable.
+/// Test that toggling LSP off, editing, and toggling back on re-syncs the
+/// server with a clean didClose+didOpen pair instead of desyncing it.
 ///
-/// This test demonstrates the root cause of issue #952:
-/// - The LSP async handler's `should_skip_did_open` returns true because
-///   `document_versions` still has the path from the first open
-/// - No `didClose` is sent when toggling LSP off, so the server still has
-///   the document open with stale content
-/// - When re-enabling, the editor inserts the handle_id into `lsp_opened_with`
-///   but the actual didOpen is never sent to the server
+/// This exercises the fix for issue #952:
+/// - Toggling LSP off now sends `didClose` for the open document and clears
+///   it from `document_versions`, instead of leaving it tracked
+/// - Toggling LSP back on unconditionally sends a fresh `didOpen` with the
+///   current buffer content (no `should_skip_did_open` gate left to trip),
+///   resetting the version counter to 0
 ///
-/// The test asserts the BUGGY behavior (only 1 didOpen). Once fixed, this
-/// test should be updated to assert 2 didOpen messages OR a didClose+didOpen pair.
+/// The test asserts the FIXED behavior: two didOpen messages (one from the
+/// initial open, one from the re-enable) and a didClose in between.
 #[test]
 #[cfg_attr(target_os = "windows", ignore)] // Uses Bash-based fake LSP server
-fn test_lsp_toggle_off_edit_toggle_on_causes_desync() -> anyhow::Result<()> {
+fn test_lsp_toggle_off_edit_toggle_on_resyncs_cleanly() -> anyhow::Result<()> {
     let _ = tracing_subscriber::fmt()
         .with_env_filter("fresh=debug")
         .try_init();
@@ -179,6 +182,8 @@ fn test_lsp_toggle_off_edit_toggle_on_causes_desync() -> anyhow::Result<()> {
             args: vec![log_file.to_string_lossy().to_string()],
             enabled: true,
             auto_start: true,
+            only_features: None,
+            except_features: None,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
         },
@@ -250,23 +255,24 @@ fn test_lsp_toggle_off_edit_toggle_on_causes_desync() -> anyhow::Result<()> {
     let final_log = std::fs::read_to_string(&log_file).unwrap_or_default();
     eprintln!("[TEST] Final LSP log:\n{}", final_log);
 
-    // Count didOpen messages
-    let did_open_count = final_log
-        .matches("METHOD:textDocument/didOpen")
-        .count();
-
-    // THE BUG: After toggle off + edit + toggle on, we need a SECOND didOpen
-    // to resync the document content. But should_skip_did_open returns true
-    // because document_versions still has the path from the first open.
-    //
-    // This assertion documents the BUGGY behavior.
-    // Once fixed, change this to assert_eq!(did_open_count, 2).
+    // Count didOpen and didClose messages.
+    let did_open_count = final_log.matches("METHOD:textDocument/didOpen").count();
+    let did_close_count = final_log.matches("METHOD:textDocument/didClose").count();
+
+    // THE FIX: toggling off sends didClose (clearing document_versions for
+    // that server/path), so toggling back on is no longer gated by
+    // should_skip_did_open and sends a second didOpen to resync the
+    // document content.
     assert_eq!(
-        did_open_count, 1,
-        "BUG REPRODUCTION: Expected exactly 1 didOpen (the re-enable didOpen is missing). \
-         Got {}. If this fails with 2, the bug may be fixed!",
+        did_open_count, 2,
+        "Expected 2 didOpen messages (initial open + re-enable resync). Got {}.",
         did_open_count
     );
+    assert_eq!(
+        did_close_count, 1,
+        "Expected 1 didClose message when toggling LSP off. Got {}.",
+        did_close_count
+    );
 
     Ok(())
 }
@@ -104,6 +104,48 @@ fn test_persistent_auto_save_fires_after_interval() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_auto_save_survives_a_leftover_interrupted_write() -> anyhow::Result<()> {
+    // Simulate a prior auto-save that was interrupted partway: the sibling
+    // temp file write_atomically uses was written but the process died
+    // before the rename that would have committed it. The real file must
+    // still be the one read back, and the next real auto-save must clean
+    // up the stale temp file via its own rename rather than leaving it
+    // behind or getting confused by it.
+    let config = auto_save_config(2);
+
+    let mut harness = EditorTestHarness::with_temp_project_and_config(80, 24, config)?;
+    let temp_dir = harness.project_dir().unwrap();
+    let file_path = temp_dir.join("test_interrupted.txt");
+    fs::write(&file_path, "Original content")?;
+
+    let temp_sibling = file_path.with_file_name(format!(
+        ".fresh-save-{}.tmp",
+        file_path.file_name().unwrap().to_string_lossy()
+    ));
+    fs::write(&temp_sibling, b"half-written content from a crashed save")?;
+
+    harness.open_file(&file_path)?;
+    harness.type_text("!")?;
+    harness.advance_time(Duration::from_millis(2100));
+
+    let saved_count = harness.editor_mut().auto_save_persistent_buffers()?;
+    assert_eq!(saved_count, 1, "auto-save should succeed despite the stale temp file");
+
+    let content = fs::read_to_string(&file_path)?;
+    assert!(
+        content.contains("!"),
+        "file should contain the real auto-saved content, not the stale temp file's. Content: {}",
+        content
+    );
+    assert!(
+        !temp_sibling.exists(),
+        "the real write should have renamed over the stale temp file, leaving none behind"
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_auto_recovery_save_throttled_before_interval() -> anyhow::Result<()> {
     let mut config = Config::default();
@@ -0,0 +1,246 @@
+//! Diagnostic report for bug reports and support.
+//!
+//! [`fresh_info`] gathers the version, install method, update status, build
+//! target, and TypeScript plugin runtime status into one [`DiagnosticReport`]
+//! that can be rendered for a terminal "about" view ([`DiagnosticReport::to_text`])
+//! or pasted into an issue template ([`DiagnosticReport::to_json`]). This
+//! consolidates information that was previously scattered across
+//! `release_checker` (version, install method) and `ts_runtime` (plugin
+//! runtime) into a single place.
+
+use crate::services::release_checker::{self, InstallMethod, ReleaseCheckResult};
+use crate::ts_runtime::{TypeScriptRuntime, REGISTERED_OP_NAMES};
+use std::path::PathBuf;
+
+/// Status of initializing the embedded deno_core/V8 plugin runtime.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TsRuntimeStatus {
+    /// The runtime initialized successfully; lists the custom ops it
+    /// registers for plugins.
+    Ok { registered_ops: Vec<String> },
+    /// Initialization failed; the error message is included verbatim.
+    Failed { error: String },
+}
+
+/// A point-in-time snapshot of everything a bug report would need: editor
+/// version, install method, update status, build target, and plugin runtime
+/// health.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiagnosticReport {
+    pub current_version: String,
+    pub install_method: InstallMethod,
+    pub executable_path: Option<PathBuf>,
+    pub target_triple: String,
+    /// `None` if no update check was performed (e.g. offline, or the
+    /// caller opted out of a network call); `Some` wraps whatever the last
+    /// check found.
+    pub update_check: Option<UpdateStatus>,
+    pub ts_runtime: TsRuntimeStatus,
+}
+
+/// The update-check portion of a report, independent of whether it came
+/// from a live check or a cached result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateStatus {
+    pub latest_version: String,
+    pub update_available: bool,
+}
+
+impl From<&ReleaseCheckResult> for UpdateStatus {
+    fn from(result: &ReleaseCheckResult) -> Self {
+        Self {
+            latest_version: result.latest_version.clone(),
+            update_available: result.update_available,
+        }
+    }
+}
+
+/// Build a diagnostic report. `update_check` is supplied by the caller
+/// (rather than performed here) so this doesn't force a network call when
+/// the report is requested from somewhere latency-sensitive; pass the
+/// result of [`release_checker::check_for_update`] or a throttled
+/// [`release_checker::maybe_start_update_check`] outcome, or `None` to skip
+/// update status entirely.
+pub fn fresh_info(update_check: Option<&ReleaseCheckResult>) -> DiagnosticReport {
+    let ts_runtime = match TypeScriptRuntime::new() {
+        Ok(_) => TsRuntimeStatus::Ok {
+            registered_ops: REGISTERED_OP_NAMES.iter().map(|s| s.to_string()).collect(),
+        },
+        Err(e) => TsRuntimeStatus::Failed { error: e.to_string() },
+    };
+
+    DiagnosticReport {
+        current_version: release_checker::CURRENT_VERSION.to_string(),
+        install_method: release_checker::detect_install_method(),
+        executable_path: std::env::current_exe().ok(),
+        target_triple: release_checker::target_triple(),
+        update_check: update_check.map(UpdateStatus::from),
+        ts_runtime,
+    }
+}
+
+impl DiagnosticReport {
+    /// Render as plain text suitable for a terminal "about/diagnostics"
+    /// view or pasting into a bug report.
+    pub fn to_text(&self) -> String {
+        let mut lines = vec![
+            format!("fresh {}", self.current_version),
+            format!("target: {}", self.target_triple),
+            format!("install method: {:?}", self.install_method),
+        ];
+
+        match &self.executable_path {
+            Some(path) => lines.push(format!("executable: {}", path.display())),
+            None => lines.push("executable: <unknown>".to_string()),
+        }
+
+        match &self.update_check {
+            Some(status) if status.update_available => {
+                lines.push(format!(
+                    "update available: {} -> {}",
+                    self.current_version, status.latest_version
+                ));
+            }
+            Some(status) => lines.push(format!("up to date (latest: {})", status.latest_version)),
+            None => lines.push("update check: not performed".to_string()),
+        }
+
+        match &self.ts_runtime {
+            TsRuntimeStatus::Ok { registered_ops } => {
+                lines.push(format!(
+                    "plugin runtime: ok ({} ops registered: {})",
+                    registered_ops.len(),
+                    registered_ops.join(", ")
+                ));
+            }
+            TsRuntimeStatus::Failed { error } => {
+                lines.push(format!("plugin runtime: failed to initialize ({error})"));
+            }
+        }
+
+        lines.join("\n")
+    }
+
+    /// Render as JSON suitable for an automated issue template. Hand-rolled
+    /// (matching `release_checker`'s own manual JSON formatting) rather than
+    /// pulling in serde for a handful of fields.
+    pub fn to_json(&self) -> String {
+        let executable_path = match &self.executable_path {
+            Some(path) => format!("\"{}\"", json_escape(&path.display().to_string())),
+            None => "null".to_string(),
+        };
+
+        let update_check = match &self.update_check {
+            Some(status) => format!(
+                r#"{{"latest_version":"{}","update_available":{}}}"#,
+                json_escape(&status.latest_version),
+                status.update_available
+            ),
+            None => "null".to_string(),
+        };
+
+        let ts_runtime = match &self.ts_runtime {
+            TsRuntimeStatus::Ok { registered_ops } => {
+                let ops = registered_ops
+                    .iter()
+                    .map(|op| format!("\"{}\"", json_escape(op)))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                format!(r#"{{"status":"ok","registered_ops":[{ops}]}}"#)
+            }
+            TsRuntimeStatus::Failed { error } => {
+                format!(r#"{{"status":"failed","error":"{}"}}"#, json_escape(error))
+            }
+        };
+
+        format!(
+            r#"{{"current_version":"{}","install_method":"{:?}","executable_path":{},"target_triple":"{}","update_check":{},"ts_runtime":{}}}"#,
+            json_escape(&self.current_version),
+            self.install_method,
+            executable_path,
+            json_escape(&self.target_triple),
+            update_check,
+            ts_runtime,
+        )
+    }
+}
+
+/// Minimal escaping for the handful of characters that can appear in the
+/// strings this module emits (paths, version tags, error messages).
+fn json_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_report() -> DiagnosticReport {
+        DiagnosticReport {
+            current_version: "0.1.0".to_string(),
+            install_method: InstallMethod::Cargo,
+            executable_path: Some(PathBuf::from("/home/user/.cargo/bin/fresh")),
+            target_triple: "x86_64-unknown-linux-gnu".to_string(),
+            update_check: Some(UpdateStatus {
+                latest_version: "0.2.0".to_string(),
+                update_available: true,
+            }),
+            ts_runtime: TsRuntimeStatus::Ok {
+                registered_ops: vec!["op_fresh_debug".to_string()],
+            },
+        }
+    }
+
+    #[test]
+    fn test_to_text_includes_update_available() {
+        let text = sample_report().to_text();
+        assert!(text.contains("update available: 0.1.0 -> 0.2.0"));
+        assert!(text.contains("plugin runtime: ok"));
+    }
+
+    #[test]
+    fn test_to_text_reports_up_to_date() {
+        let mut report = sample_report();
+        report.update_check = Some(UpdateStatus {
+            latest_version: "0.1.0".to_string(),
+            update_available: false,
+        });
+        assert!(report.to_text().contains("up to date (latest: 0.1.0)"));
+    }
+
+    #[test]
+    fn test_to_text_reports_runtime_failure() {
+        let mut report = sample_report();
+        report.ts_runtime = TsRuntimeStatus::Failed {
+            error: "boom".to_string(),
+        };
+        assert!(report.to_text().contains("plugin runtime: failed to initialize (boom)"));
+    }
+
+    #[test]
+    fn test_to_json_round_trips_expected_fields() {
+        let json = sample_report().to_json();
+        assert!(json.contains(r#""current_version":"0.1.0""#));
+        assert!(json.contains(r#""update_available":true"#));
+        assert!(json.contains(r#""status":"ok""#));
+        assert!(json.contains(r#""op_fresh_debug""#));
+    }
+
+    #[test]
+    fn test_to_json_escapes_special_characters_in_error() {
+        let mut report = sample_report();
+        report.ts_runtime = TsRuntimeStatus::Failed {
+            error: "line1\nline2 with \"quotes\"".to_string(),
+        };
+        let json = report.to_json();
+        assert!(json.contains(r#""error":"line1\nline2 with \"quotes\"""#));
+    }
+
+    #[test]
+    fn test_fresh_info_populates_version_and_runtime_status() {
+        let report = fresh_info(None);
+        assert_eq!(report.current_version, release_checker::CURRENT_VERSION);
+        assert!(report.update_check.is_none());
+        assert!(matches!(report.ts_runtime, TsRuntimeStatus::Ok { .. }));
+    }
+}
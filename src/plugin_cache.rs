@@ -0,0 +1,314 @@
+//! Remote TypeScript plugin loading with a local, content-addressed cache.
+//!
+//! [`TypeScriptRuntime::load_remote_module`] lets a plugin be installed
+//! straight from a GitHub release asset or a raw `https://` URL instead of
+//! only from the local filesystem. Downloads are written once into a cache
+//! directory keyed by a hash of the source URL and served from disk on
+//! every later load, so a plugin keeps working offline once installed.
+
+use anyhow::{anyhow, Context, Result};
+use deno_core::{
+    ModuleLoadResponse, ModuleLoader, ModuleSource, ModuleSourceCode, ModuleSpecifier, ModuleType,
+    RequestedModuleType, ResolutionKind,
+};
+use sha2::{Digest, Sha256};
+use std::env;
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+
+use crate::services::release_checker::{self, InstallMethod};
+
+/// `$XDG_CACHE_HOME/fresh/plugins` (or the platform equivalent), mirroring
+/// [`release_checker`]'s config-dir convention but under the cache dir
+/// instead, since these entries are disposable.
+pub fn plugin_cache_dir() -> PathBuf {
+    let cache_dir = if cfg!(windows) {
+        env::var_os("LOCALAPPDATA").map(PathBuf::from)
+    } else {
+        env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|h| PathBuf::from(h).join(".cache")))
+    }
+    .unwrap_or_else(env::temp_dir);
+
+    cache_dir.join("fresh").join("plugins")
+}
+
+/// Delete every cached plugin download.
+pub fn clear_plugin_cache() -> Result<()> {
+    let dir = plugin_cache_dir();
+    match fs::remove_dir_all(&dir) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(anyhow!("failed to clear plugin cache at {:?}: {}", dir, e)),
+    }
+}
+
+/// Stable, content-addressed key for a source URL. Not cryptographic by
+/// itself (it's only used to name a file); the optional expected integrity
+/// hash that [`fetch_and_cache`] checks is what protects against tampering.
+/// No hash is stored with the cached file.
+fn cache_key_for_url(url: &str) -> String {
+    let digest = Sha256::digest(url.as_bytes());
+    hex_encode(&digest)
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hex_encode(&Sha256::digest(bytes))
+}
+
+/// Where `url` would be cached on disk, without fetching or checking
+/// whether it is actually there yet.
+fn cached_path_for_url(url: &str) -> PathBuf {
+    plugin_cache_dir().join(cache_key_for_url(url))
+}
+
+/// Download `url`'s bytes into the cache (if not already present), checking
+/// `expected_integrity` (a hex-encoded SHA-256, as returned by
+/// [`sha256_hex`]) when given. Returns the path to the cached file.
+fn fetch_and_cache(url: &str, expected_integrity: Option<&str>) -> Result<PathBuf> {
+    let dest = cached_path_for_url(url);
+    if dest.exists() {
+        if let Some(expected) = expected_integrity {
+            let cached = fs::read(&dest).context("failed to read cached plugin for integrity check")?;
+            let actual = sha256_hex(&cached);
+            if !actual.eq_ignore_ascii_case(expected) {
+                return Err(anyhow!(
+                    "integrity check failed for cached copy of {}: expected {}, got {}",
+                    url,
+                    expected,
+                    actual
+                ));
+            }
+        }
+        return Ok(dest);
+    }
+
+    let bytes = ureq::get(url)
+        .call()
+        .map_err(|e| anyhow!("failed to download plugin from {}: {}", url, e))?
+        .into_reader()
+        .bytes()
+        .collect::<std::io::Result<Vec<u8>>>()
+        .context("failed to read plugin response body")?;
+
+    if let Some(expected) = expected_integrity {
+        let actual = sha256_hex(&bytes);
+        if !actual.eq_ignore_ascii_case(expected) {
+            return Err(anyhow!(
+                "integrity check failed for {}: expected {}, got {}",
+                url,
+                expected,
+                actual
+            ));
+        }
+    }
+
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent).context("failed to create plugin cache dir")?;
+    }
+    fs::write(&dest, &bytes).context("failed to write cached plugin")?;
+    Ok(dest)
+}
+
+/// Resolve the download URL for a plugin install request: either a pinned
+/// URL (used as-is) or `owner/repo@tag`/`owner/repo` (resolved against the
+/// GitHub releases API, reusing the same JSON parsing the update checker
+/// uses for its own release assets).
+pub enum PluginSource {
+    /// A direct `https://` URL to the module (or its entry point).
+    Url(String),
+    /// A GitHub repo, and either a pinned tag or "latest".
+    GitHubRelease {
+        repo: String,
+        tag: Option<String>,
+        asset_name: String,
+    },
+}
+
+impl PluginSource {
+    /// Resolve the download URL, and (for a GitHub release) the published
+    /// digest for the matching asset if the API response includes one.
+    /// A pinned `Url` source has no digest to check against, since nothing
+    /// published one for it.
+    fn resolve_url_and_integrity(&self) -> Result<(String, Option<String>)> {
+        match self {
+            PluginSource::Url(url) => Ok((url.clone(), None)),
+            PluginSource::GitHubRelease {
+                repo,
+                tag,
+                asset_name,
+            } => {
+                let api_url = match tag {
+                    Some(tag) => format!("https://api.github.com/repos/{repo}/releases/tags/{tag}"),
+                    None => format!("https://api.github.com/repos/{repo}/releases/latest"),
+                };
+                let json = release_checker::fetch_release_json(&api_url)
+                    .map_err(|e| anyhow!("failed to fetch release metadata for {}: {}", repo, e))?;
+                let url = release_checker::parse_asset_url_from_json(&json, asset_name)
+                    .map_err(|e| anyhow!("failed to find asset {} for {}: {}", asset_name, repo, e))?;
+                let digest = release_checker::parse_asset_digest_from_json(&json, asset_name);
+                Ok((url, digest))
+            }
+        }
+    }
+}
+
+/// Install `source` into the plugin cache (downloading it if it isn't
+/// already cached), and return the path to the cached module on disk.
+///
+/// `expected_integrity`, if given, overrides whatever digest `source`
+/// itself resolves to (e.g. a caller that pinned a known-good hash out of
+/// band). Otherwise a `GitHubRelease` source's own published asset digest
+/// is used when the release API provided one.
+pub fn install_plugin(source: &PluginSource, expected_integrity: Option<&str>) -> Result<PathBuf> {
+    let (url, resolved_integrity) = source.resolve_url_and_integrity()?;
+    let integrity = expected_integrity.map(str::to_string).or(resolved_integrity);
+    fetch_and_cache(&url, integrity.as_deref())
+}
+
+/// A [`ModuleLoader`] that resolves `https://` specifiers through the
+/// plugin cache (downloading transitively-imported remote modules the same
+/// way) and falls back to the filesystem for everything else.
+pub struct CachedRemoteModuleLoader {
+    fs_loader: deno_core::FsModuleLoader,
+}
+
+impl CachedRemoteModuleLoader {
+    pub fn new() -> Self {
+        Self {
+            fs_loader: deno_core::FsModuleLoader,
+        }
+    }
+}
+
+impl Default for CachedRemoteModuleLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ModuleLoader for CachedRemoteModuleLoader {
+    fn resolve(&self, specifier: &str, referrer: &str, kind: ResolutionKind) -> Result<ModuleSpecifier> {
+        if specifier.starts_with("https://") || specifier.starts_with("http://") {
+            return ModuleSpecifier::parse(specifier)
+                .map_err(|e| anyhow!("invalid remote module specifier '{}': {}", specifier, e));
+        }
+        self.fs_loader.resolve(specifier, referrer, kind)
+    }
+
+    fn load(
+        &self,
+        module_specifier: &ModuleSpecifier,
+        maybe_referrer: Option<&ModuleSpecifier>,
+        is_dyn_import: bool,
+        requested_module_type: RequestedModuleType,
+    ) -> ModuleLoadResponse {
+        if module_specifier.scheme() != "https" && module_specifier.scheme() != "http" {
+            return self
+                .fs_loader
+                .load(module_specifier, maybe_referrer, is_dyn_import, requested_module_type);
+        }
+
+        let url = module_specifier.to_string();
+        let specifier = module_specifier.clone();
+        ModuleLoadResponse::Async(Box::pin(async move {
+            let path = fetch_and_cache(&url, None)?;
+            let code = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read cached plugin module at {:?}", path))?;
+            Ok(ModuleSource::new(
+                ModuleType::JavaScript,
+                ModuleSourceCode::String(code.into()),
+                &specifier,
+                None,
+            ))
+        }))
+    }
+}
+
+/// Build a [`PluginSource`] for `owner/repo`'s asset named `asset_name`,
+/// resolved via the "latest" release. The name is used as given; no
+/// platform-specific asset name is derived here.
+pub fn github_latest_asset(repo: &str, asset_name: &str) -> PluginSource {
+    PluginSource::GitHubRelease {
+        repo: repo.to_string(),
+        tag: None,
+        asset_name: asset_name.to_string(),
+    }
+}
+
+#[allow(dead_code)]
+fn install_method_hint() -> InstallMethod {
+    // Kept for parity with release_checker's own diagnostics; remote plugin
+    // installs don't currently vary by install method.
+    release_checker::detect_install_method()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_key_is_stable_and_url_specific() {
+        let a = cache_key_for_url("https://example.com/plugin.js");
+        let b = cache_key_for_url("https://example.com/plugin.js");
+        let c = cache_key_for_url("https://example.com/other.js");
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_sha256_hex_matches_known_vector() {
+        // sha256("") = e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855
+        assert_eq!(
+            sha256_hex(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn test_fetch_and_cache_rejects_tampered_integrity() {
+        let dir = plugin_cache_dir();
+        let _ = fs::create_dir_all(&dir);
+        let url = "https://example.invalid/does-not-matter-because-precached.js";
+        let dest = cached_path_for_url(url);
+        fs::write(&dest, b"totally legit plugin code").unwrap();
+
+        let result = fetch_and_cache(url, Some("0000000000000000000000000000000000000000000000000000000000000000"));
+        // A cache hit is still hashed against expected_integrity, so a file
+        // swapped out underneath the cache (or corrupted on disk) is
+        // rejected instead of served.
+        assert!(result.is_err());
+
+        fs::remove_file(&dest).ok();
+    }
+
+    #[test]
+    fn test_fetch_and_cache_serves_cache_hit_matching_integrity() {
+        let dir = plugin_cache_dir();
+        let _ = fs::create_dir_all(&dir);
+        let url = "https://example.invalid/matches-its-own-hash.js";
+        let dest = cached_path_for_url(url);
+        fs::write(&dest, b"totally legit plugin code").unwrap();
+
+        let result = fetch_and_cache(url, Some(&sha256_hex(b"totally legit plugin code")));
+        assert!(result.is_ok());
+
+        fs::remove_file(&dest).ok();
+    }
+
+    #[test]
+    fn test_clear_plugin_cache_removes_directory() {
+        let dir = plugin_cache_dir();
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("leftover"), b"x").unwrap();
+
+        clear_plugin_cache().unwrap();
+        assert!(!dir.exists());
+    }
+}
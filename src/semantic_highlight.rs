@@ -8,18 +8,21 @@
 //! - Computed on-demand during rendering (no persistent markers)
 //! - Only highlights occurrences within the visible viewport
 //!
-//! # Future Enhancement: Tree-sitter Scope-Aware Highlighting
-//! Currently uses text matching to find occurrences. A better approach would be
-//! to use tree-sitter's "locals" queries to find only semantically-related
-//! identifiers (same variable binding). This would:
-//! - Not highlight `x` in one function when cursor is on `x` in another function
-//! - Respect lexical scoping rules
-//! - Match how VSCode's documentHighlight works
-//!
-//! This would require:
-//! - Running tree-sitter locals queries for each language
-//! - Building a symbol table mapping definitions to references
-//! - Tracking scope boundaries
+//! # Scope-Aware Highlighting
+//! [`SemanticHighlighter::scope_aware`] switches `highlight_occurrences` from
+//! naive whole-word text matching to resolving the word under the cursor to
+//! a single variable binding, the way editors' documentHighlight does — see
+//! [`scope_resolution`] for the binding-resolution algorithm itself. This
+//! checkout has no tree-sitter grammars or `locals.scm` queries vendored for
+//! any language, so [`SemanticHighlighter::scope_captures_for_buffer`] (the
+//! hook that would run a language's locals query against the buffer's parse
+//! tree) runs [`scope_resolution::build_naive_scope_captures`] instead — a
+//! brace-nesting/keyword heuristic, not a real parse, but enough to resolve
+//! the common `let`/`fn`-bound case without a grammar dependency. Once
+//! captures are available, `highlight_occurrences` highlights nothing
+//! (rather than falling back to text matching) when the word under the
+//! cursor doesn't resolve to a binding — matching how a real locals query
+//! would behave.
 
 use crate::highlighter::HighlightSpan;
 use crate::text_buffer::Buffer;
@@ -27,10 +30,433 @@ use crate::word_navigation::{find_word_end, find_word_start, is_word_char};
 use ratatui::style::Color;
 use std::ops::Range;
 
+/// Scope-aware occurrence resolution over tree-sitter `locals.scm` captures.
+///
+/// A real integration would run a language's `locals.scm` query against the
+/// buffer's parse tree to produce a [`ScopeCaptures`] value; this module is
+/// the part of the request that's independent of which parser produced it —
+/// given scopes, definitions, and references, resolve the word under the
+/// cursor to a binding and collect every capture that resolves to the same
+/// binding.
+pub mod scope_resolution {
+    use std::ops::Range;
+
+    /// A lexical scope: the byte range of the node that opened it (e.g. a
+    /// function body or block), and its parent scope, if any. Scopes must be
+    /// properly nested — `scopes[i].range` must be fully contained in
+    /// `scopes[parent].range` when `parent` is `Some`.
+    #[derive(Debug, Clone)]
+    pub struct Scope {
+        pub range: Range<usize>,
+        pub parent: Option<usize>,
+    }
+
+    /// An identifier captured as `@local.definition` or `@local.reference`,
+    /// owned by the scope it was declared or used directly in (not merely
+    /// contained by, since a nested inner scope's own definitions shadow it).
+    #[derive(Debug, Clone)]
+    pub struct Identifier {
+        pub range: Range<usize>,
+        pub text: String,
+        pub scope: usize,
+    }
+
+    /// The `@local.scope`/`@local.definition`/`@local.reference` captures a
+    /// `locals.scm` query produced for a buffer (or the portion of it
+    /// relevant to the current highlight request).
+    #[derive(Debug, Clone, Default)]
+    pub struct ScopeCaptures {
+        pub scopes: Vec<Scope>,
+        pub definitions: Vec<Identifier>,
+        pub references: Vec<Identifier>,
+    }
+
+    impl ScopeCaptures {
+        /// Find the identifier capture (definition or reference) whose range
+        /// covers `position`, if any.
+        fn identifier_at(&self, position: usize) -> Option<&Identifier> {
+            self.definitions
+                .iter()
+                .chain(self.references.iter())
+                .find(|ident| ident.range.contains(&position) || ident.range.end == position)
+        }
+
+        /// Resolve `name` to its binding definition, searching outward from
+        /// `scope` through each enclosing parent. Returns the index into
+        /// [`ScopeCaptures::definitions`] of the nearest definition owned
+        /// directly by `scope` or an ancestor of it — an inner definition
+        /// shadows any same-named definition further out, since the search
+        /// stops at the first scope with a match.
+        fn resolve_in_scope(&self, scope: usize, name: &str) -> Option<usize> {
+            let mut current = Some(scope);
+            while let Some(scope_index) = current {
+                if let Some(def_index) = self
+                    .definitions
+                    .iter()
+                    .position(|def| def.scope == scope_index && def.text == name)
+                {
+                    return Some(def_index);
+                }
+                current = self.scopes[scope_index].parent;
+            }
+            None
+        }
+
+        /// Resolve the identifier at `position` (a definition or a
+        /// reference) to its binding definition. Returns `None` if
+        /// `position` isn't on a captured identifier at all, or the
+        /// identifier has no definition anywhere in its scope chain —
+        /// both cases mean "nothing to highlight", not "highlight
+        /// everything with this spelling".
+        pub fn resolve_binding(&self, position: usize) -> Option<usize> {
+            let identifier = self.identifier_at(position)?;
+            self.resolve_in_scope(identifier.scope, &identifier.text)
+        }
+
+        /// Every span that resolves to the same binding as `definition_index`
+        /// — the definition itself plus every reference that resolves to
+        /// it — restricted to `viewport`.
+        pub fn occurrences_of(&self, definition_index: usize, viewport: Range<usize>) -> Vec<Range<usize>> {
+            let definition = &self.definitions[definition_index];
+            let mut spans = Vec::new();
+
+            if overlaps(&definition.range, &viewport) {
+                spans.push(definition.range.clone());
+            }
+
+            for reference in &self.references {
+                if !overlaps(&reference.range, &viewport) {
+                    continue;
+                }
+                if self.resolve_in_scope(reference.scope, &reference.text) == Some(definition_index) {
+                    spans.push(reference.range.clone());
+                }
+            }
+
+            spans.sort_by_key(|range| range.start);
+            spans
+        }
+    }
+
+    fn overlaps(range: &Range<usize>, viewport: &Range<usize>) -> bool {
+        range.start < viewport.end && range.end > viewport.start
+    }
+
+    /// Keywords that introduce a binding in one of the common C-like/Rust/JS
+    /// languages this checkout has no tree-sitter grammar for — the
+    /// identifier immediately following one of these is a `@local.definition`
+    /// rather than a `@local.reference`.
+    const DEFINITION_KEYWORDS: &[&str] = &["let", "const", "var", "fn", "function", "def"];
+
+    /// Build [`ScopeCaptures`] for `text` without a real parser: `{`/`}`
+    /// nesting stands in for `@local.scope` boundaries, and an identifier
+    /// immediately after one of [`DEFINITION_KEYWORDS`] is a
+    /// `@local.definition`; every other identifier is a `@local.reference`.
+    /// This is deliberately not a real `locals.scm` query — it doesn't
+    /// understand strings, comments, or per-language binding forms beyond
+    /// the keyword list above — but it's enough to resolve the common case
+    /// (a `let`/`fn`-bound name and its uses within the same braces) without
+    /// vendoring a grammar, so `scope_aware` highlighting has something real
+    /// to run instead of always falling back to text matching.
+    pub fn build_naive_scope_captures(text: &str) -> ScopeCaptures {
+        let mut captures = ScopeCaptures { scopes: vec![Scope { range: 0..text.len(), parent: None }], ..Default::default() };
+        let mut scope_stack = vec![0usize];
+        let tokens: Vec<(Range<usize>, String)> = tokenize_identifiers(text).collect();
+
+        let mut scan_from = 0;
+        let mut i = 0;
+        while i < tokens.len() {
+            let (range, word) = tokens[i].clone();
+
+            // Close/open scopes for every brace between the end of the
+            // previous token (or start of text) and this one.
+            for (byte_pos, ch) in text[scan_from..range.start].char_indices() {
+                let pos = scan_from + byte_pos;
+                match ch {
+                    '{' => {
+                        let parent = *scope_stack.last().unwrap();
+                        let new_scope = captures.scopes.len();
+                        captures.scopes.push(Scope { range: pos..text.len(), parent: Some(parent) });
+                        scope_stack.push(new_scope);
+                    }
+                    '}' if scope_stack.len() > 1 => {
+                        scope_stack.pop();
+                    }
+                    _ => {}
+                }
+            }
+            scan_from = range.end;
+
+            let current_scope = *scope_stack.last().unwrap();
+            if DEFINITION_KEYWORDS.contains(&word.as_str()) {
+                if let Some((def_range, def_word)) = tokens.get(i + 1).cloned() {
+                    captures.definitions.push(Identifier { range: def_range.clone(), text: def_word, scope: current_scope });
+                    scan_from = def_range.end;
+                    i += 1;
+                }
+            } else {
+                captures.references.push(Identifier { range, text: word, scope: current_scope });
+            }
+            i += 1;
+        }
+
+        captures
+    }
+
+    /// Scan `text` for `[A-Za-z_][A-Za-z0-9_]*` runs, returning each with its
+    /// byte range.
+    fn tokenize_identifiers(text: &str) -> impl Iterator<Item = (Range<usize>, String)> + '_ {
+        let bytes = text.as_bytes();
+        let mut start = 0;
+        std::iter::from_fn(move || {
+            while start < bytes.len() {
+                if is_identifier_start(bytes[start]) {
+                    let begin = start;
+                    while start < bytes.len() && is_identifier_continue(bytes[start]) {
+                        start += 1;
+                    }
+                    return Some((begin..start, text[begin..start].to_string()));
+                }
+                start += 1;
+            }
+            None
+        })
+    }
+
+    fn is_identifier_start(b: u8) -> bool {
+        b.is_ascii_alphabetic() || b == b'_'
+    }
+
+    fn is_identifier_continue(b: u8) -> bool {
+        b.is_ascii_alphanumeric() || b == b'_'
+    }
+
+    #[cfg(test)]
+    mod naive_capture_tests {
+        use super::*;
+
+        #[test]
+        fn test_resolves_simple_let_binding_in_one_scope() {
+            let text = "fn main() { let count = 0; print(count); }";
+            let captures = build_naive_scope_captures(text);
+            let def_index = captures
+                .definitions
+                .iter()
+                .position(|d| d.text == "count")
+                .expect("count should be captured as a definition");
+            let binding = captures.resolve_binding(captures.definitions[def_index].range.start);
+            assert_eq!(binding, Some(def_index));
+        }
+
+        #[test]
+        fn test_inner_scope_definition_shadows_outer() {
+            let text = "{ let x = 1; { let x = 2; use_x(x); } }";
+            let captures = build_naive_scope_captures(text);
+            let inner_use = captures.references.iter().find(|r| r.text == "x").expect("x reference");
+            let resolved = captures.resolve_binding(inner_use.range.start).expect("should resolve");
+            let inner_def = captures
+                .definitions
+                .iter()
+                .enumerate()
+                .filter(|(_, d)| d.text == "x")
+                .max_by_key(|(_, d)| d.range.start)
+                .map(|(i, _)| i)
+                .unwrap();
+            assert_eq!(resolved, inner_def);
+        }
+
+        #[test]
+        fn test_unbound_reference_resolves_to_none() {
+            let text = "print(missing_binding);";
+            let captures = build_naive_scope_captures(text);
+            let reference = captures.references.iter().find(|r| r.text == "missing_binding").unwrap();
+            assert_eq!(captures.resolve_binding(reference.range.start), None);
+        }
+    }
+}
+
+use scope_resolution::ScopeCaptures;
+
+/// How many bytes [`SemanticHighlighter::next_occurrence`]/`prev_occurrence`
+/// scan per step when searching outward from the cursor — small enough
+/// that a nearby hit in a large buffer doesn't require scanning the whole
+/// thing, large enough to keep the chunk count low for typical files.
+const NAVIGATION_CHUNK_BYTES: usize = 4096;
+
+/// Which way [`SemanticHighlighter::navigate_occurrence`] searches from the
+/// cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NavigationDirection {
+    Forward,
+    Backward,
+}
+
+/// Scope-aware counterpart of the chunked text-based navigation: resolve
+/// the cursor to a binding via `captures`, then step to the next/previous
+/// capture that resolves to the same binding, wrapping around the ends of
+/// the capture list. Captures cover the whole file already (a locals query
+/// runs over the full parse tree), so no chunking is needed here the way
+/// the byte-offset text scan requires.
+fn navigate_scope_occurrence(
+    captures: &ScopeCaptures,
+    cursor_position: usize,
+    direction: NavigationDirection,
+) -> Option<Range<usize>> {
+    let binding = captures.resolve_binding(cursor_position)?;
+    let occurrences = captures.occurrences_of(binding, 0..usize::MAX);
+    let current = occurrences
+        .iter()
+        .find(|r| r.contains(&cursor_position) || r.end == cursor_position)?
+        .clone();
+
+    match direction {
+        NavigationDirection::Forward => occurrences
+            .iter()
+            .find(|r| r.start >= current.end)
+            .or_else(|| occurrences.iter().find(|r| r.start < current.start))
+            .cloned(),
+        NavigationDirection::Backward => occurrences
+            .iter()
+            .rev()
+            .find(|r| r.start < current.start)
+            .or_else(|| occurrences.iter().rev().find(|r| r.start >= current.end))
+            .cloned(),
+    }
+}
+
+/// The three kinds `textDocument/documentHighlight` tags its ranges with.
+/// LSP encodes these as the integers 1/2/3; see
+/// <https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_documentHighlight>.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentHighlightKind {
+    /// A textual occurrence with no further semantic meaning.
+    Text,
+    /// A read of the binding (most occurrences).
+    Read,
+    /// A write/assignment to the binding — rendered more prominently so the
+    /// mutation sites stand out from plain reads.
+    Write,
+}
+
+impl DocumentHighlightKind {
+    /// Parse the LSP wire value (1, 2, or 3). Returns `None` for anything
+    /// else, since a server returning an out-of-range kind shouldn't crash
+    /// the renderer.
+    pub fn from_lsp_value(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(DocumentHighlightKind::Text),
+            2 => Some(DocumentHighlightKind::Read),
+            3 => Some(DocumentHighlightKind::Write),
+            _ => None,
+        }
+    }
+}
+
+/// One range from a `textDocument/documentHighlight` response, already
+/// translated from LSP's UTF-16 line/character position into this buffer's
+/// byte offsets.
+#[derive(Debug, Clone)]
+pub struct LspHighlight {
+    pub range: Range<usize>,
+    pub kind: DocumentHighlightKind,
+}
+
+/// Debounces `textDocument/documentHighlight` requests on cursor movement
+/// and keys the cached response to the cursor position it was requested
+/// for, so a response that arrives after the cursor has moved on is
+/// discarded rather than shown in the wrong place.
+///
+/// The render loop that would own one of these, issue the LSP request, and
+/// feed the result back via [`store`](Self::store) doesn't exist in this
+/// checkout (there's no LSP client here at all) — this is the
+/// backend-independent bookkeeping that loop would drive.
+/// [`should_request_for`](Self::should_request_for) is the one piece of that
+/// decision that *is* wireable today: it adds routing awareness via
+/// [`crate::services::lsp::LanguageServers`] to the plain debounce check in
+/// [`should_request`](Self::should_request), so a render loop never debounces
+/// its way into firing a request no attached server would even answer.
+#[derive(Debug, Default)]
+pub struct DocumentHighlightCache {
+    cached: Option<(usize, Vec<LspHighlight>)>,
+    idle_since: Option<(usize, std::time::Instant)>,
+}
+
+impl DocumentHighlightCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the cursor's current position. Discards any cached result
+    /// keyed to a different position and restarts the idle timer if the
+    /// cursor just arrived at this position.
+    pub fn note_cursor_moved(&mut self, cursor_position: usize) {
+        if self.cached.as_ref().map(|(pos, _)| *pos) != Some(cursor_position) {
+            self.cached = None;
+        }
+        if self.idle_since.as_ref().map(|(pos, _)| *pos) != Some(cursor_position) {
+            self.idle_since = Some((cursor_position, std::time::Instant::now()));
+        }
+    }
+
+    /// [`should_request`](Self::should_request), gated additionally on
+    /// whether any server attached to the buffer actually supports
+    /// `textDocument/documentHighlight` — via
+    /// [`LanguageServers::route`](crate::services::lsp::LanguageServers::route).
+    /// Without this, a render loop driven only by the debounce timer would
+    /// keep "requesting" from a server that was never going to answer,
+    /// instead of falling back to [`SemanticHighlighter::highlight_occurrences`]
+    /// immediately.
+    pub fn should_request_for(
+        &self,
+        cursor_position: usize,
+        debounce: std::time::Duration,
+        servers: &crate::services::lsp::LanguageServers,
+    ) -> bool {
+        servers.route(crate::services::lsp::LspFeature::DocumentHighlight).is_some()
+            && self.should_request(cursor_position, debounce)
+    }
+
+    /// Whether the cursor has been idle at `cursor_position` for at least
+    /// `debounce` with no fresh cached result yet — the signal a render
+    /// loop should use to decide whether to fire a new request.
+    pub fn should_request(&self, cursor_position: usize, debounce: std::time::Duration) -> bool {
+        if self.cached.as_ref().map(|(pos, _)| *pos) == Some(cursor_position) {
+            return false;
+        }
+        match &self.idle_since {
+            Some((pos, since)) if *pos == cursor_position => since.elapsed() >= debounce,
+            _ => false,
+        }
+    }
+
+    /// Store a response for `cursor_position`. Ignored if the cursor has
+    /// since moved to a different position, since the response no longer
+    /// describes where the cursor is.
+    pub fn store(&mut self, cursor_position: usize, highlights: Vec<LspHighlight>) {
+        let still_current = matches!(&self.idle_since, Some((pos, _)) if *pos == cursor_position);
+        if still_current {
+            self.cached = Some((cursor_position, highlights));
+        }
+    }
+
+    /// The cached response for `cursor_position`, if any.
+    pub fn get(&self, cursor_position: usize) -> Option<&[LspHighlight]> {
+        self.cached.as_ref().filter(|(pos, _)| *pos == cursor_position).map(|(_, h)| h.as_slice())
+    }
+}
+
 /// Default subtle background color for occurrence highlights
 /// A dark gray that's visible but not distracting
 pub const DEFAULT_HIGHLIGHT_COLOR: Color = Color::Rgb(60, 60, 80);
 
+/// Default background color for LSP-reported read occurrences — the same
+/// subtle gray as text-matched occurrences.
+pub const DEFAULT_READ_COLOR: Color = Color::Rgb(60, 60, 80);
+
+/// Default background color for LSP-reported write/assignment occurrences
+/// — warmer and more saturated so mutations stand out from reads.
+pub const DEFAULT_WRITE_COLOR: Color = Color::Rgb(110, 70, 40);
+
 /// Semantic highlighter for word occurrences
 pub struct SemanticHighlighter {
     /// Color for occurrence highlights
@@ -39,6 +465,18 @@ pub struct SemanticHighlighter {
     pub min_word_length: usize,
     /// Whether semantic highlighting is enabled
     pub enabled: bool,
+    /// When true, resolve the word under the cursor to a single variable
+    /// binding via scope captures and highlight only its
+    /// semantically-related references, instead of every textual match.
+    /// The captures come from the brace/keyword heuristic for every
+    /// language; text matching is used only when the buffer is not valid
+    /// UTF-8 — see [`scope_captures_for_buffer`](Self::scope_captures_for_buffer).
+    pub scope_aware: bool,
+    /// Background color for LSP-reported read occurrences.
+    pub read_color: Color,
+    /// Background color for LSP-reported write/assignment occurrences —
+    /// should be more prominent than `read_color` so mutations stand out.
+    pub write_color: Color,
 }
 
 impl SemanticHighlighter {
@@ -48,6 +486,9 @@ impl SemanticHighlighter {
             highlight_color: DEFAULT_HIGHLIGHT_COLOR,
             min_word_length: 2,
             enabled: true,
+            scope_aware: false,
+            read_color: DEFAULT_READ_COLOR,
+            write_color: DEFAULT_WRITE_COLOR,
         }
     }
 
@@ -63,6 +504,196 @@ impl SemanticHighlighter {
         self
     }
 
+    /// Enable or disable scope-aware resolution.
+    pub fn with_scope_aware(mut self, scope_aware: bool) -> Self {
+        self.scope_aware = scope_aware;
+        self
+    }
+
+    /// Set the read-occurrence color.
+    pub fn with_read_color(mut self, color: Color) -> Self {
+        self.read_color = color;
+        self
+    }
+
+    /// Set the write-occurrence color.
+    pub fn with_write_color(mut self, color: Color) -> Self {
+        self.write_color = color;
+        self
+    }
+
+    /// Get highlights for word occurrences in the viewport, preferring an
+    /// already-fetched LSP `textDocument/documentHighlight` response when
+    /// one is given (non-empty), and otherwise falling back to
+    /// [`highlight_occurrences`](Self::highlight_occurrences)'s scope-aware
+    /// or text-matching behavior — the same fallback that applies when the
+    /// server is disabled or returned nothing.
+    ///
+    /// `lsp_highlights` is expected to already be keyed to `cursor_position`
+    /// (e.g. via [`DocumentHighlightCache::get`]); this method doesn't
+    /// re-check staleness itself.
+    pub fn highlight_occurrences_with_lsp(
+        &self,
+        buffer: &Buffer,
+        cursor_position: usize,
+        viewport_start: usize,
+        viewport_end: usize,
+        lsp_highlights: Option<&[LspHighlight]>,
+    ) -> Vec<HighlightSpan> {
+        if !self.enabled {
+            return Vec::new();
+        }
+
+        if let Some(highlights) = lsp_highlights.filter(|h| !h.is_empty()) {
+            let viewport = viewport_start..viewport_end;
+            return highlights
+                .iter()
+                .filter(|h| h.range.start < viewport.end && h.range.end > viewport.start)
+                .map(|h| HighlightSpan {
+                    range: h.range.clone(),
+                    color: match h.kind {
+                        DocumentHighlightKind::Write => self.write_color,
+                        DocumentHighlightKind::Read | DocumentHighlightKind::Text => self.read_color,
+                    },
+                })
+                .collect();
+        }
+
+        self.highlight_occurrences(buffer, cursor_position, viewport_start, viewport_end)
+    }
+
+    /// Run the buffer's language's `locals.scm` query (`@local.scope`,
+    /// `@local.definition`, `@local.reference`) against its parse tree to
+    /// produce scope-resolution captures.
+    ///
+    /// This checkout has no tree-sitter grammars or `.scm` query files
+    /// vendored for any language, so instead of a real parse this runs
+    /// [`scope_resolution::build_naive_scope_captures`], a brace-nesting and
+    /// keyword-based approximation — good enough to resolve common
+    /// `let`/`fn`-style bindings without a grammar dependency. Returns `None`
+    /// only if the buffer's bytes aren't valid UTF-8, in which case
+    /// `highlight_occurrences` falls back to text matching.
+    fn scope_captures_for_buffer(&self, buffer: &Buffer) -> Option<ScopeCaptures> {
+        let bytes = buffer.slice_bytes(0..buffer.len());
+        let text = std::str::from_utf8(&bytes).ok()?;
+        Some(scope_resolution::build_naive_scope_captures(text))
+    }
+
+    /// Move to the next occurrence of the word under the cursor, searching
+    /// the entire buffer rather than just the viewport `highlight_occurrences`
+    /// is limited to, wrapping around to the start of the buffer if nothing
+    /// is found before the end. Returns `None` if the cursor isn't on a
+    /// word, the word is shorter than `min_word_length`, or it has no other
+    /// occurrences anywhere in the buffer.
+    ///
+    /// When `scope_aware` is on and [`scope_captures_for_buffer`](Self::scope_captures_for_buffer)
+    /// yields captures (any valid UTF-8 buffer), only
+    /// references bound to the same definition as the cursor are visited
+    /// (see [`scope_resolution`]) — a lightweight "go to next use of this
+    /// variable" without a full LSP references round-trip. Otherwise this
+    /// falls back to the same whole-word text matching
+    /// [`highlight_occurrences`](Self::highlight_occurrences) uses.
+    pub fn next_occurrence(&self, buffer: &Buffer, cursor_position: usize) -> Option<Range<usize>> {
+        self.navigate_occurrence(buffer, cursor_position, NavigationDirection::Forward)
+    }
+
+    /// The backward counterpart of [`next_occurrence`](Self::next_occurrence).
+    pub fn prev_occurrence(&self, buffer: &Buffer, cursor_position: usize) -> Option<Range<usize>> {
+        self.navigate_occurrence(buffer, cursor_position, NavigationDirection::Backward)
+    }
+
+    fn navigate_occurrence(
+        &self,
+        buffer: &Buffer,
+        cursor_position: usize,
+        direction: NavigationDirection,
+    ) -> Option<Range<usize>> {
+        if self.scope_aware {
+            if let Some(captures) = self.scope_captures_for_buffer(buffer) {
+                return navigate_scope_occurrence(&captures, cursor_position, direction);
+            }
+        }
+
+        let word_range = self.get_word_at_position(buffer, cursor_position)?;
+        let word_bytes = buffer.slice_bytes(word_range.clone());
+        let word = std::str::from_utf8(&word_bytes).ok()?.to_string();
+        if word.len() < self.min_word_length {
+            return None;
+        }
+
+        self.find_adjacent_whole_word(buffer, &word, word_range, direction)
+    }
+
+    /// Find the nearest whole-word occurrence of `word` in `direction`
+    /// relative to `current` (the word range at the cursor), wrapping
+    /// around the buffer ends. Scans in [`NAVIGATION_CHUNK_BYTES`]-sized
+    /// windows outward from `current` via [`find_occurrences_in_range`]
+    /// (which already pads each window so a match straddling a chunk
+    /// boundary isn't missed) so a nearby hit in a large buffer doesn't pay
+    /// the cost of a full-buffer scan.
+    fn find_adjacent_whole_word(
+        &self,
+        buffer: &Buffer,
+        word: &str,
+        current: Range<usize>,
+        direction: NavigationDirection,
+    ) -> Option<Range<usize>> {
+        let buf_len = buffer.len();
+        match direction {
+            NavigationDirection::Forward => self
+                .scan_chunks_forward(buffer, word, current.end..buf_len, |r| r.start >= current.end)
+                .or_else(|| self.scan_chunks_forward(buffer, word, 0..current.start, |r| r.start < current.start)),
+            NavigationDirection::Backward => self
+                .scan_chunks_backward(buffer, word, 0..current.start, |r| r.start < current.start)
+                .or_else(|| self.scan_chunks_backward(buffer, word, current.end..buf_len, |r| r.start >= current.end)),
+        }
+    }
+
+    /// Scan `range` in increasing [`NAVIGATION_CHUNK_BYTES`]-sized windows,
+    /// returning the first occurrence of `word` (in byte order) that
+    /// satisfies `predicate`.
+    fn scan_chunks_forward(
+        &self,
+        buffer: &Buffer,
+        word: &str,
+        range: Range<usize>,
+        predicate: impl Fn(&Range<usize>) -> bool,
+    ) -> Option<Range<usize>> {
+        let mut chunk_start = range.start;
+        while chunk_start < range.end {
+            let chunk_end = (chunk_start + NAVIGATION_CHUNK_BYTES).min(range.end);
+            let occurrences = self.find_occurrences_in_range(buffer, word, chunk_start, chunk_end);
+            if let Some(found) = occurrences.into_iter().find(&predicate) {
+                return Some(found);
+            }
+            chunk_start = chunk_end;
+        }
+        None
+    }
+
+    /// The backward counterpart of
+    /// [`scan_chunks_forward`](Self::scan_chunks_forward): scans `range` in
+    /// decreasing windows, returning the occurrence closest to `range.end`
+    /// that satisfies `predicate`.
+    fn scan_chunks_backward(
+        &self,
+        buffer: &Buffer,
+        word: &str,
+        range: Range<usize>,
+        predicate: impl Fn(&Range<usize>) -> bool,
+    ) -> Option<Range<usize>> {
+        let mut chunk_end = range.end;
+        while chunk_end > range.start {
+            let chunk_start = chunk_end.saturating_sub(NAVIGATION_CHUNK_BYTES).max(range.start);
+            let occurrences = self.find_occurrences_in_range(buffer, word, chunk_start, chunk_end);
+            if let Some(found) = occurrences.into_iter().rev().find(&predicate) {
+                return Some(found);
+            }
+            chunk_end = chunk_start;
+        }
+        None
+    }
+
     /// Get highlights for word occurrences in the viewport
     ///
     /// # Arguments
@@ -84,6 +715,25 @@ impl SemanticHighlighter {
             return Vec::new();
         }
 
+        if self.scope_aware {
+            if let Some(captures) = self.scope_captures_for_buffer(buffer) {
+                let viewport = viewport_start..viewport_end;
+                return match captures.resolve_binding(cursor_position) {
+                    Some(definition_index) => captures
+                        .occurrences_of(definition_index, viewport)
+                        .into_iter()
+                        .map(|range| HighlightSpan {
+                            range,
+                            color: self.highlight_color,
+                        })
+                        .collect(),
+                    // The word under the cursor isn't bound anywhere: highlight
+                    // nothing rather than falling back to textual matches.
+                    None => Vec::new(),
+                };
+            }
+        }
+
         // Find the word under the cursor
         let word_range = match self.get_word_at_position(buffer, cursor_position) {
             Some(range) => range,
@@ -231,6 +881,38 @@ impl Default for SemanticHighlighter {
     }
 }
 
+/// A keybinding-dispatchable "jump to occurrence" command, one variant per
+/// direction. This is the single entry point a keymap action handler would
+/// call — [`OccurrenceNavigationCommand::target`] is the only thing such a
+/// handler needs: given the highlighter, buffer, and cursor, where should
+/// the cursor move? There's no action/keymap registry anywhere in this
+/// checkout to register `next_occurrence`/`prev_occurrence` under action
+/// names (no `enum Action`, no default keymap, and no `Editor` for a
+/// handler method to live on — see the crate-level gaps noted in
+/// `app/view_actions.rs`), so wiring a real key to this command is the one
+/// step left once that registry exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OccurrenceNavigationCommand {
+    Next,
+    Prev,
+}
+
+impl OccurrenceNavigationCommand {
+    /// Resolve this command against `highlighter`/`buffer`/`cursor_position`,
+    /// returning the byte range the cursor should move to, if any.
+    pub fn target(
+        &self,
+        highlighter: &SemanticHighlighter,
+        buffer: &Buffer,
+        cursor_position: usize,
+    ) -> Option<Range<usize>> {
+        match self {
+            OccurrenceNavigationCommand::Next => highlighter.next_occurrence(buffer, cursor_position),
+            OccurrenceNavigationCommand::Prev => highlighter.prev_occurrence(buffer, cursor_position),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -340,4 +1022,405 @@ mod tests {
         assert_eq!(spans.len(), 1);
         assert_eq!(spans[0].range, 8..11);
     }
+
+    #[test]
+    fn test_scope_aware_resolves_let_binding_via_naive_captures() {
+        // No tree-sitter grammar is vendored in this checkout, so
+        // scope_captures_for_buffer runs build_naive_scope_captures instead
+        // of a real locals.scm query; it still resolves "foo"'s definition
+        // and both uses here, landing on the same 3 occurrences text
+        // matching would have found.
+        let buffer = Buffer::from_str_test("let foo = 1;\nlet bar = foo;\nlet baz = foo;");
+        let highlighter = SemanticHighlighter::new().with_scope_aware(true);
+
+        let spans = highlighter.highlight_occurrences(&buffer, 4, 0, buffer.len());
+        assert_eq!(spans.len(), 3);
+    }
+
+    #[test]
+    fn test_scope_aware_does_not_cross_contaminate_unrelated_same_named_binding() {
+        // "fn a() { let v = 1; use_a(v); } fn b() { let v = 2; use_b(v); }"
+        //           ^13 = the "v" defined inside `a`              ^32 = "fn b()"
+        // Unlike text matching, scope-aware resolution must not pull in the
+        // unrelated `v` binding from `b`'s sibling scope.
+        let buffer = Buffer::from_str_test("fn a() { let v = 1; use_a(v); } fn b() { let v = 2; use_b(v); }");
+        let highlighter = SemanticHighlighter::new().with_scope_aware(true);
+
+        let spans = highlighter.highlight_occurrences(&buffer, 13, 0, buffer.len());
+
+        // Only the definition and use inside `a`'s braces, not `b`'s.
+        assert_eq!(spans.len(), 2);
+        assert!(spans.iter().all(|s| s.range.start < 32));
+    }
+
+    mod scope_resolution_tests {
+        use super::super::scope_resolution::{Identifier, Scope, ScopeCaptures};
+
+        // Models:
+        //   fn outer() {       // scope 0 (function body), 0..60
+        //       let x = 1;     // definition "x" @ 10..11, owned by scope 0
+        //       {              // scope 1 (block), 20..50
+        //           let x = 2; // definition "x" @ 30..31, owned by scope 1 (shadows outer x)
+        //           use(x);    // reference "x" @ 40..41, resolves to inner x (30..31)
+        //       }
+        //       use(x);        // reference "x" @ 55..56, resolves to outer x (10..11)
+        //   }
+        pub(super) fn shadowing_captures() -> ScopeCaptures {
+            ScopeCaptures {
+                scopes: vec![
+                    Scope { range: 0..60, parent: None },
+                    Scope { range: 20..50, parent: Some(0) },
+                ],
+                definitions: vec![
+                    Identifier { range: 10..11, text: "x".to_string(), scope: 0 },
+                    Identifier { range: 30..31, text: "x".to_string(), scope: 1 },
+                ],
+                references: vec![
+                    Identifier { range: 40..41, text: "x".to_string(), scope: 1 },
+                    Identifier { range: 55..56, text: "x".to_string(), scope: 0 },
+                ],
+            }
+        }
+
+        #[test]
+        fn test_reference_resolves_to_innermost_shadowing_definition() {
+            let captures = shadowing_captures();
+            let binding = captures.resolve_binding(40).unwrap();
+            assert_eq!(captures.definitions[binding].range, 30..31);
+        }
+
+        #[test]
+        fn test_reference_resolves_to_outer_definition_outside_shadow() {
+            let captures = shadowing_captures();
+            let binding = captures.resolve_binding(55).unwrap();
+            assert_eq!(captures.definitions[binding].range, 10..11);
+        }
+
+        #[test]
+        fn test_cursor_on_definition_resolves_to_itself() {
+            let captures = shadowing_captures();
+            let binding = captures.resolve_binding(30).unwrap();
+            assert_eq!(captures.definitions[binding].range, 30..31);
+        }
+
+        #[test]
+        fn test_occurrences_of_inner_binding_excludes_outer_shadowed_uses() {
+            let captures = shadowing_captures();
+            let binding = captures.resolve_binding(40).unwrap();
+            let occurrences = captures.occurrences_of(binding, 0..60);
+            assert_eq!(occurrences, vec![30..31, 40..41]);
+        }
+
+        #[test]
+        fn test_occurrences_of_outer_binding_excludes_shadowed_inner_uses() {
+            let captures = shadowing_captures();
+            let binding = captures.resolve_binding(55).unwrap();
+            let occurrences = captures.occurrences_of(binding, 0..60);
+            assert_eq!(occurrences, vec![10..11, 55..56]);
+        }
+
+        #[test]
+        fn test_unbound_identifier_resolves_to_nothing() {
+            // A reference with no matching definition anywhere in its scope
+            // chain (e.g. a global/builtin `y` this locals query never saw
+            // defined) must not resolve — and so highlight nothing, not
+            // "every mention of y".
+            let mut captures = shadowing_captures();
+            captures.references.push(Identifier { range: 58..59, text: "y".to_string(), scope: 0 });
+            assert_eq!(captures.resolve_binding(58), None);
+        }
+
+        #[test]
+        fn test_position_not_on_any_capture_resolves_to_nothing() {
+            let captures = shadowing_captures();
+            // Byte 15 is whitespace/punctuation between captures.
+            assert_eq!(captures.resolve_binding(15), None);
+        }
+
+        #[test]
+        fn test_occurrences_of_respects_viewport() {
+            let captures = shadowing_captures();
+            let binding = captures.resolve_binding(55).unwrap();
+            // Viewport excludes the outer definition at 10..11.
+            let occurrences = captures.occurrences_of(binding, 20..60);
+            assert_eq!(occurrences, vec![55..56]);
+        }
+    }
+
+    mod lsp_highlight_tests {
+        use super::*;
+
+        #[test]
+        fn test_document_highlight_kind_from_lsp_value() {
+            assert_eq!(DocumentHighlightKind::from_lsp_value(1), Some(DocumentHighlightKind::Text));
+            assert_eq!(DocumentHighlightKind::from_lsp_value(2), Some(DocumentHighlightKind::Read));
+            assert_eq!(DocumentHighlightKind::from_lsp_value(3), Some(DocumentHighlightKind::Write));
+            assert_eq!(DocumentHighlightKind::from_lsp_value(4), None);
+        }
+
+        #[test]
+        fn test_prefers_lsp_highlights_with_read_write_colors() {
+            let buffer = Buffer::from_str_test("let foo = 1;\nfoo = 2;\nlet bar = foo;");
+            let highlighter = SemanticHighlighter::new();
+
+            let lsp_highlights = vec![
+                LspHighlight { range: 4..7, kind: DocumentHighlightKind::Write },
+                LspHighlight { range: 13..16, kind: DocumentHighlightKind::Write },
+                LspHighlight { range: 32..35, kind: DocumentHighlightKind::Read },
+            ];
+
+            let spans = highlighter.highlight_occurrences_with_lsp(&buffer, 4, 0, buffer.len(), Some(&lsp_highlights));
+
+            assert_eq!(spans.len(), 3);
+            assert_eq!(spans[0].color, highlighter.write_color);
+            assert_eq!(spans[1].color, highlighter.write_color);
+            assert_eq!(spans[2].color, highlighter.read_color);
+        }
+
+        #[test]
+        fn test_lsp_highlights_restricted_to_viewport() {
+            let buffer = Buffer::from_str_test("let foo = 1;\nfoo = 2;\nlet bar = foo;");
+            let highlighter = SemanticHighlighter::new();
+
+            let lsp_highlights = vec![
+                LspHighlight { range: 4..7, kind: DocumentHighlightKind::Write },
+                LspHighlight { range: 13..16, kind: DocumentHighlightKind::Write },
+                LspHighlight { range: 32..35, kind: DocumentHighlightKind::Read },
+            ];
+
+            // Viewport only covers the first line.
+            let spans = highlighter.highlight_occurrences_with_lsp(&buffer, 4, 0, 13, Some(&lsp_highlights));
+            assert_eq!(spans.len(), 1);
+            assert_eq!(spans[0].range, 4..7);
+        }
+
+        #[test]
+        fn test_falls_back_to_text_matching_when_lsp_returns_nothing() {
+            let buffer = Buffer::from_str_test("let foo = 1;\nlet bar = foo;");
+            let highlighter = SemanticHighlighter::new();
+
+            let spans = highlighter.highlight_occurrences_with_lsp(&buffer, 4, 0, buffer.len(), Some(&[]));
+            assert_eq!(spans.len(), 2);
+        }
+
+        #[test]
+        fn test_falls_back_to_text_matching_when_no_lsp_response_given() {
+            let buffer = Buffer::from_str_test("let foo = 1;\nlet bar = foo;");
+            let highlighter = SemanticHighlighter::new();
+
+            let spans = highlighter.highlight_occurrences_with_lsp(&buffer, 4, 0, buffer.len(), None);
+            assert_eq!(spans.len(), 2);
+        }
+
+        #[test]
+        fn test_disabled_highlighter_ignores_lsp_highlights_too() {
+            let buffer = Buffer::from_str_test("let foo = 1;");
+            let mut highlighter = SemanticHighlighter::new();
+            highlighter.enabled = false;
+
+            let lsp_highlights = vec![LspHighlight { range: 4..7, kind: DocumentHighlightKind::Write }];
+            let spans = highlighter.highlight_occurrences_with_lsp(&buffer, 4, 0, buffer.len(), Some(&lsp_highlights));
+            assert_eq!(spans.len(), 0);
+        }
+
+        #[test]
+        fn test_cache_discards_result_for_stale_cursor_position() {
+            let mut cache = DocumentHighlightCache::new();
+            cache.note_cursor_moved(10);
+            cache.note_cursor_moved(20);
+
+            // A response for the old position (10) arrives after the
+            // cursor already moved on to 20 — it must not be cached.
+            cache.store(10, vec![LspHighlight { range: 10..13, kind: DocumentHighlightKind::Read }]);
+            assert!(cache.get(10).is_none());
+            assert!(cache.get(20).is_none());
+        }
+
+        #[test]
+        fn test_cache_stores_result_for_current_cursor_position() {
+            let mut cache = DocumentHighlightCache::new();
+            cache.note_cursor_moved(10);
+            cache.store(10, vec![LspHighlight { range: 10..13, kind: DocumentHighlightKind::Read }]);
+
+            let cached = cache.get(10).unwrap();
+            assert_eq!(cached.len(), 1);
+            assert_eq!(cached[0].range, 10..13);
+        }
+
+        #[test]
+        fn test_cache_invalidated_when_cursor_moves_away_and_back() {
+            let mut cache = DocumentHighlightCache::new();
+            cache.note_cursor_moved(10);
+            cache.store(10, vec![LspHighlight { range: 10..13, kind: DocumentHighlightKind::Read }]);
+            assert!(cache.get(10).is_some());
+
+            cache.note_cursor_moved(20);
+            assert!(cache.get(10).is_none(), "moving away should discard the old position's cache");
+            assert!(cache.get(20).is_none(), "no response has been stored for the new position yet");
+        }
+
+        #[test]
+        fn test_should_request_waits_for_debounce_then_only_fires_once() {
+            let mut cache = DocumentHighlightCache::new();
+            cache.note_cursor_moved(10);
+
+            assert!(!cache.should_request(10, std::time::Duration::from_secs(60)));
+            assert!(cache.should_request(10, std::time::Duration::from_secs(0)));
+
+            cache.store(10, vec![]);
+            // A cached result (even an empty one) means no more requests
+            // are needed until the cursor moves again.
+            assert!(!cache.should_request(10, std::time::Duration::from_secs(0)));
+        }
+
+        #[test]
+        fn test_should_request_for_is_false_with_no_server_routed_for_document_highlight() {
+            use crate::services::lsp::LanguageServers;
+
+            let mut cache = DocumentHighlightCache::new();
+            cache.note_cursor_moved(10);
+
+            let servers = LanguageServers::new(vec![]);
+            assert!(!cache.should_request_for(10, std::time::Duration::from_secs(0), &servers));
+        }
+
+        #[test]
+        fn test_should_request_for_defers_to_debounce_once_a_server_is_routed() {
+            use crate::services::lsp::{LanguageServers, LspServerConfig};
+
+            let mut cache = DocumentHighlightCache::new();
+            cache.note_cursor_moved(10);
+
+            let server = LspServerConfig {
+                command: "rust-analyzer".to_string(),
+                args: vec![],
+                enabled: true,
+                auto_start: true,
+                only_features: None,
+                except_features: None,
+                process_limits: Default::default(),
+                initialization_options: None,
+            };
+            let servers = LanguageServers::new(vec![server]);
+
+            assert!(!cache.should_request_for(10, std::time::Duration::from_secs(60), &servers));
+            assert!(cache.should_request_for(10, std::time::Duration::from_secs(0), &servers));
+        }
+    }
+
+    mod navigation_tests {
+        use super::*;
+
+        #[test]
+        fn test_next_occurrence_moves_forward() {
+            let buffer = Buffer::from_str_test("let foo = 1;\nlet bar = foo;\nlet baz = foo;");
+            let highlighter = SemanticHighlighter::new();
+
+            // Cursor on the first "foo" (byte 4).
+            let next = highlighter.next_occurrence(&buffer, 4).unwrap();
+            assert_eq!(next, 23..26);
+        }
+
+        #[test]
+        fn test_next_occurrence_wraps_around_buffer_end() {
+            let buffer = Buffer::from_str_test("let foo = 1;\nlet bar = foo;\nlet baz = foo;");
+            let highlighter = SemanticHighlighter::new();
+
+            // Cursor on the last "foo" (38..41) — next should wrap to the first.
+            let next = highlighter.next_occurrence(&buffer, 40).unwrap();
+            assert_eq!(next, 4..7);
+        }
+
+        #[test]
+        fn test_prev_occurrence_moves_backward() {
+            let buffer = Buffer::from_str_test("let foo = 1;\nlet bar = foo;\nlet baz = foo;");
+            let highlighter = SemanticHighlighter::new();
+
+            let prev = highlighter.prev_occurrence(&buffer, 40).unwrap();
+            assert_eq!(prev, 23..26);
+        }
+
+        #[test]
+        fn test_prev_occurrence_wraps_around_buffer_start() {
+            let buffer = Buffer::from_str_test("let foo = 1;\nlet bar = foo;\nlet baz = foo;");
+            let highlighter = SemanticHighlighter::new();
+
+            // Cursor on the first "foo" — prev should wrap to the last.
+            let prev = highlighter.prev_occurrence(&buffer, 4).unwrap();
+            assert_eq!(prev, 38..41);
+        }
+
+        #[test]
+        fn test_next_occurrence_none_when_word_occurs_only_once() {
+            let buffer = Buffer::from_str_test("let foo = 1;");
+            let highlighter = SemanticHighlighter::new();
+
+            assert_eq!(highlighter.next_occurrence(&buffer, 4), None);
+            assert_eq!(highlighter.prev_occurrence(&buffer, 4), None);
+        }
+
+        #[test]
+        fn test_next_occurrence_none_when_cursor_not_on_a_word() {
+            let buffer = Buffer::from_str_test("foo bar foo");
+            let highlighter = SemanticHighlighter::new();
+
+            // Byte 3 is the space between "foo" and "bar".
+            assert_eq!(highlighter.next_occurrence(&buffer, 3), None);
+        }
+
+        #[test]
+        fn test_navigation_scans_across_chunk_boundaries() {
+            // Force several NAVIGATION_CHUNK_BYTES-sized windows between
+            // occurrences so the chunked scan must actually advance past
+            // empty chunks rather than finding everything in one window.
+            let padding = "x".repeat(NAVIGATION_CHUNK_BYTES * 2);
+            let text = format!("foo {padding} foo");
+            let buffer = Buffer::from_str_test(&text);
+            let highlighter = SemanticHighlighter::new();
+
+            let next = highlighter.next_occurrence(&buffer, 0).unwrap();
+            assert_eq!(next, text.len() - 3..text.len());
+        }
+
+        #[test]
+        fn test_scope_aware_navigation_only_visits_same_binding() {
+            // Reuses the shadowing fixture from scope_resolution_tests:
+            // reference at 40..41 is bound to the inner x (30..31), the
+            // reference at 55..56 is bound to the outer x (10..11).
+            // Navigating forward from the inner definition must visit only
+            // the inner reference, never the outer definition/reference.
+            let captures = super::scope_resolution_tests::shadowing_captures();
+            let next = navigate_scope_occurrence(&captures, 30, NavigationDirection::Forward).unwrap();
+            assert_eq!(next, 40..41);
+
+            // From the inner reference, forward wraps back to the inner
+            // definition, not the outer pair.
+            let wrapped = navigate_scope_occurrence(&captures, 40, NavigationDirection::Forward).unwrap();
+            assert_eq!(wrapped, 30..31);
+        }
+
+        #[test]
+        fn test_scope_aware_navigation_wraps_within_two_occurrence_binding() {
+            let captures = super::scope_resolution_tests::shadowing_captures();
+            // The outer reference (55..56) is the only other occurrence of
+            // the outer binding besides its definition, so forward from the
+            // definition lands on it, and forward again wraps back.
+            let next = navigate_scope_occurrence(&captures, 10, NavigationDirection::Forward).unwrap();
+            assert_eq!(next, 55..56);
+            let wrapped = navigate_scope_occurrence(&captures, 55, NavigationDirection::Forward).unwrap();
+            assert_eq!(wrapped, 10..11);
+        }
+
+        #[test]
+        fn test_occurrence_navigation_command_dispatches_next_and_prev() {
+            let buffer = Buffer::from_str_test("let foo = 1;\nlet bar = foo;\nlet baz = foo;");
+            let highlighter = SemanticHighlighter::new();
+
+            let next = OccurrenceNavigationCommand::Next.target(&highlighter, &buffer, 4).unwrap();
+            assert_eq!(next, highlighter.next_occurrence(&buffer, 4).unwrap());
+
+            let prev = OccurrenceNavigationCommand::Prev.target(&highlighter, &buffer, 4).unwrap();
+            assert_eq!(prev, highlighter.prev_occurrence(&buffer, 4).unwrap());
+        }
+    }
 }
@@ -0,0 +1,45 @@
+//! Resource limits applied to a spawned language-server process.
+//!
+//! This checkout has no process-spawning layer to enforce these against —
+//! [`ProcessLimits`] is the plain config data [`super::lsp::LspServerConfig`]
+//! carries per server; whoever adds process management would read it when
+//! spawning and supervising the child.
+
+use std::time::Duration;
+
+/// Limits on a single language-server child process.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessLimits {
+    /// Maximum resident memory before the process is killed and restarted,
+    /// or `None` for no limit.
+    pub max_memory_bytes: Option<u64>,
+    /// How long to wait for the process to respond to `initialize` before
+    /// treating the launch as failed.
+    pub startup_timeout: Duration,
+    /// How many times to restart the process after an unexpected exit
+    /// before giving up and leaving it stopped.
+    pub max_restarts: u32,
+}
+
+impl Default for ProcessLimits {
+    fn default() -> Self {
+        Self {
+            max_memory_bytes: None,
+            startup_timeout: Duration::from_secs(10),
+            max_restarts: 3,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_has_no_memory_limit_and_allows_restarts() {
+        let limits = ProcessLimits::default();
+        assert_eq!(limits.max_memory_bytes, None);
+        assert_eq!(limits.max_restarts, 3);
+        assert_eq!(limits.startup_timeout, Duration::from_secs(10));
+    }
+}
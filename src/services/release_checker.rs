@@ -5,11 +5,14 @@
 //! - Detect the installation method (Homebrew, npm, cargo, etc.) based on executable path
 //! - Provide appropriate update commands based on installation method
 
+use sha2::{Digest, Sha256};
 use std::env;
-use std::path::Path;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
 use std::thread::{self, JoinHandle};
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// The current version of the editor
 pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -115,19 +118,10 @@ pub fn start_update_check(releases_url: &str) -> UpdateCheckHandle {
 /// Fetches release information from the provided URL.
 pub fn fetch_latest_version(url: &str) -> Result<String, String> {
     tracing::debug!("Fetching latest version from {}", url);
-    let response = ureq::get(url)
-        .set("User-Agent", "fresh-editor-update-checker")
-        .set("Accept", "application/vnd.github.v3+json")
-        .timeout(Duration::from_secs(5))
-        .call()
-        .map_err(|e| {
-            tracing::debug!("HTTP request failed: {}", e);
-            format!("HTTP request failed: {}", e)
-        })?;
-
-    let body = response
-        .into_string()
-        .map_err(|e| format!("Failed to read response body: {}", e))?;
+    let body = fetch_release_json(url).map_err(|e| {
+        tracing::debug!("HTTP request failed: {}", e);
+        e
+    })?;
 
     let version = parse_version_from_json(&body)?;
     tracing::debug!("Latest version: {}", version);
@@ -219,29 +213,554 @@ fn is_arch_linux() -> bool {
 /// Compare two semantic versions
 /// Returns true if `latest` is newer than `current`
 pub fn is_newer_version(current: &str, latest: &str) -> bool {
-    let parse_version = |v: &str| -> Option<(u32, u32, u32)> {
-        let parts: Vec<&str> = v.split('.').collect();
-        if parts.len() >= 3 {
-            Some((
-                parts[0].parse().ok()?,
-                parts[1].parse().ok()?,
-                parts[2].split('-').next()?.parse().ok()?,
-            ))
-        } else if parts.len() == 2 {
-            Some((parts[0].parse().ok()?, parts[1].parse().ok()?, 0))
-        } else {
-            None
+    match (SemVer::parse(current), SemVer::parse(latest)) {
+        (Some(c), Some(l)) => l > c,
+        _ => false,
+    }
+}
+
+/// One dot-separated identifier within a prerelease component, e.g. `alpha`
+/// or `1` in `1.0.0-alpha.1`.
+///
+/// Per semver's precedence rules, purely-numeric identifiers compare
+/// numerically and always sort before alphanumeric ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PreReleaseIdent {
+    Numeric(u64),
+    AlphaNumeric(String),
+}
+
+impl PartialOrd for PreReleaseIdent {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PreReleaseIdent {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        use PreReleaseIdent::*;
+        match (self, other) {
+            (Numeric(a), Numeric(b)) => a.cmp(b),
+            (AlphaNumeric(a), AlphaNumeric(b)) => a.cmp(b),
+            (Numeric(_), AlphaNumeric(_)) => std::cmp::Ordering::Less,
+            (AlphaNumeric(_), Numeric(_)) => std::cmp::Ordering::Greater,
         }
-    };
+    }
+}
+
+/// A parsed `major.minor.patch[-prerelease][+build]` version, ordered per
+/// semver precedence (<https://semver.org/#spec-item-11>): numeric fields
+/// compare first, a prerelease version always has lower precedence than the
+/// same version without one, and build metadata never affects ordering.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SemVer {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    pre: Vec<PreReleaseIdent>,
+}
+
+impl SemVer {
+    /// Parse a (possibly `v`-prefixed) version string. Missing minor/patch
+    /// components default to zero, matching this project's historically
+    /// loose `major.minor` release tags.
+    fn parse(v: &str) -> Option<Self> {
+        let v = v.strip_prefix('v').unwrap_or(v);
+        // Build metadata has no bearing on precedence; discard it outright.
+        let v = v.split('+').next().unwrap_or(v);
+
+        let (core, pre) = match v.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (v, None),
+        };
+
+        let mut parts = core.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next().unwrap_or("0").parse().ok()?;
+        let patch = parts.next().unwrap_or("0").parse().ok()?;
+
+        let pre = pre
+            .map(|pre| {
+                pre.split('.')
+                    .map(|ident| match ident.parse::<u64>() {
+                        Ok(n) => PreReleaseIdent::Numeric(n),
+                        Err(_) => PreReleaseIdent::AlphaNumeric(ident.to_string()),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Some(Self {
+            major,
+            minor,
+            patch,
+            pre,
+        })
+    }
+}
+
+impl PartialOrd for SemVer {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
-    match (parse_version(current), parse_version(latest)) {
-        (Some((c_major, c_minor, c_patch)), Some((l_major, l_minor, l_patch))) => {
-            (l_major, l_minor, l_patch) > (c_major, c_minor, c_patch)
+impl Ord for SemVer {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => std::cmp::Ordering::Equal,
+                // A version with no prerelease outranks one that has one.
+                (true, false) => std::cmp::Ordering::Greater,
+                (false, true) => std::cmp::Ordering::Less,
+                (false, false) => self.pre.cmp(&other.pre),
+            })
+    }
+}
+
+/// Error returned by [`self_upgrade`].
+#[derive(Debug)]
+pub enum SelfUpgradeError {
+    /// The release asset for this build's target couldn't be fetched.
+    Download(String),
+    /// The downloaded archive's SHA-256 didn't match the one GitHub
+    /// published for it alongside the asset.
+    Integrity(String),
+    /// The downloaded archive couldn't be extracted into a binary.
+    Extract(String),
+    /// The running executable couldn't be located or replaced.
+    Replace(String),
+}
+
+impl std::fmt::Display for SelfUpgradeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SelfUpgradeError::Download(e) => write!(f, "download failed: {e}"),
+            SelfUpgradeError::Integrity(e) => write!(f, "integrity check failed: {e}"),
+            SelfUpgradeError::Extract(e) => write!(f, "extract failed: {e}"),
+            SelfUpgradeError::Replace(e) => write!(f, "replacing executable failed: {e}"),
         }
-        _ => false,
     }
 }
 
+impl std::error::Error for SelfUpgradeError {}
+
+/// Compile-time target triple, e.g. `x86_64-unknown-linux-gnu`.
+///
+/// `TARGET` is only set when a build script forwards it (cargo doesn't set
+/// it for the crate being built itself); fall back to a triple assembled
+/// from `std::env::consts` so this still works without one.
+pub(crate) fn target_triple() -> String {
+    option_env!("TARGET")
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS))
+}
+
+/// The release asset filename for the current build's target.
+fn asset_name_for_target() -> String {
+    let target = target_triple();
+    if cfg!(windows) {
+        format!("fresh-{target}.zip")
+    } else {
+        format!("fresh-{target}.tar.gz")
+    }
+}
+
+/// Pull the whole release JSON body (used by both `fetch_latest_version`
+/// and `self_upgrade`, which additionally needs the asset list).
+pub(crate) fn fetch_release_json(url: &str) -> Result<String, String> {
+    let response = ureq::get(url)
+        .set("User-Agent", "fresh-editor-update-checker")
+        .set("Accept", "application/vnd.github.v3+json")
+        .timeout(Duration::from_secs(5))
+        .call()
+        .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+    response.into_string().map_err(|e| format!("Failed to read response body: {}", e))
+}
+
+/// Find the `browser_download_url` of the asset named `asset_name` in a
+/// GitHub releases API JSON response.
+pub(crate) fn parse_asset_url_from_json(json: &str, asset_name: &str) -> Result<String, String> {
+    let name_needle = format!("\"{}\"", asset_name);
+    let name_pos = json
+        .find(&name_needle)
+        .ok_or_else(|| format!("no release asset named '{asset_name}'"))?;
+
+    let url_key = "\"browser_download_url\"";
+    let url_rel = json[name_pos..]
+        .find(url_key)
+        .ok_or_else(|| format!("asset '{asset_name}' has no browser_download_url"))?;
+    let after_key = &json[name_pos + url_rel + url_key.len()..];
+
+    let value_start = after_key
+        .find('"')
+        .ok_or_else(|| "Invalid JSON: missing quote after browser_download_url".to_string())?;
+    let value_content = &after_key[value_start + 1..];
+    let value_end = value_content
+        .find('"')
+        .ok_or_else(|| "Invalid JSON: unclosed quote".to_string())?;
+
+    Ok(value_content[..value_end].to_string())
+}
+
+/// Find the `digest` of the asset named `asset_name` in a GitHub releases
+/// API JSON response, as a hex-encoded SHA-256 (GitHub publishes this as
+/// `"digest": "sha256:<hex>"`). Returns `None` if the API response has no
+/// `digest` field for that asset (older release, not all asset types carry
+/// one) rather than erroring, since the caller should still install the
+/// plugin and just skip integrity verification in that case.
+pub(crate) fn parse_asset_digest_from_json(json: &str, asset_name: &str) -> Option<String> {
+    let name_needle = format!("\"{}\"", asset_name);
+    let name_pos = json.find(&name_needle)?;
+
+    let digest_key = "\"digest\"";
+    let digest_rel = json[name_pos..].find(digest_key)?;
+    let after_key = &json[name_pos + digest_rel + digest_key.len()..];
+
+    let value_start = after_key.find('"')?;
+    let value_content = &after_key[value_start + 1..];
+    let value_end = value_content.find('"')?;
+    let digest = &value_content[..value_end];
+
+    digest.strip_prefix("sha256:").map(|hex| hex.to_string())
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hex_encode(&Sha256::digest(bytes))
+}
+
+/// Verify `path`'s contents hash to `expected` (a hex-encoded SHA-256, as
+/// published in the release JSON's `digest` field). Deleting the file on a
+/// mismatch is the caller's responsibility, since what to clean up depends
+/// on whether the path is the final archive or something extracted from it.
+fn verify_digest(path: &Path, expected: &str) -> Result<(), SelfUpgradeError> {
+    let bytes = fs::read(path).map_err(|e| SelfUpgradeError::Integrity(e.to_string()))?;
+    let actual = sha256_hex(&bytes);
+    if actual.eq_ignore_ascii_case(expected) {
+        Ok(())
+    } else {
+        Err(SelfUpgradeError::Integrity(format!(
+            "expected {expected}, got {actual}"
+        )))
+    }
+}
+
+/// Download `url` to `dest`, streaming the response body directly to disk.
+fn download_to_file(url: &str, dest: &Path) -> Result<(), SelfUpgradeError> {
+    let response = ureq::get(url)
+        .set("User-Agent", "fresh-editor-update-checker")
+        .timeout(Duration::from_secs(60))
+        .call()
+        .map_err(|e| SelfUpgradeError::Download(format!("{url}: {e}")))?;
+
+    let mut file = fs::File::create(dest).map_err(|e| SelfUpgradeError::Download(e.to_string()))?;
+    std::io::copy(&mut response.into_reader(), &mut file).map_err(|e| SelfUpgradeError::Download(e.to_string()))?;
+    Ok(())
+}
+
+/// Extract the `fresh` binary from the downloaded archive into `dest_dir`,
+/// returning its path. `.tar.gz` on unix, `.zip` on Windows.
+#[cfg(unix)]
+fn extract_binary(archive: &Path, dest_dir: &Path) -> Result<PathBuf, SelfUpgradeError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let file = fs::File::open(archive).map_err(|e| SelfUpgradeError::Extract(e.to_string()))?;
+    let decoder = flate2::read::GzDecoder::new(file);
+    let mut tar = tar::Archive::new(decoder);
+
+    let extracted = dest_dir.join(".fresh-update-extracted");
+    let _ = fs::remove_file(&extracted);
+
+    for entry in tar.entries().map_err(|e| SelfUpgradeError::Extract(e.to_string()))? {
+        let mut entry = entry.map_err(|e| SelfUpgradeError::Extract(e.to_string()))?;
+        let path = entry.path().map_err(|e| SelfUpgradeError::Extract(e.to_string()))?;
+        if path.file_name().and_then(|n| n.to_str()) == Some("fresh") {
+            entry
+                .unpack(&extracted)
+                .map_err(|e| SelfUpgradeError::Extract(e.to_string()))?;
+            let mut perms = fs::metadata(&extracted)
+                .map_err(|e| SelfUpgradeError::Extract(e.to_string()))?
+                .permissions();
+            perms.set_mode(0o755);
+            fs::set_permissions(&extracted, perms).map_err(|e| SelfUpgradeError::Extract(e.to_string()))?;
+            return Ok(extracted);
+        }
+    }
+    Err(SelfUpgradeError::Extract("archive did not contain a 'fresh' binary".to_string()))
+}
+
+#[cfg(windows)]
+fn extract_binary(archive: &Path, dest_dir: &Path) -> Result<PathBuf, SelfUpgradeError> {
+    let file = fs::File::open(archive).map_err(|e| SelfUpgradeError::Extract(e.to_string()))?;
+    let mut zip = zip::ZipArchive::new(file).map_err(|e| SelfUpgradeError::Extract(e.to_string()))?;
+
+    let extracted = dest_dir.join(".fresh-update-extracted.exe");
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i).map_err(|e| SelfUpgradeError::Extract(e.to_string()))?;
+        if entry.name().ends_with("fresh.exe") {
+            let mut out = fs::File::create(&extracted).map_err(|e| SelfUpgradeError::Extract(e.to_string()))?;
+            std::io::copy(&mut entry, &mut out).map_err(|e| SelfUpgradeError::Extract(e.to_string()))?;
+            return Ok(extracted);
+        }
+    }
+    Err(SelfUpgradeError::Extract("archive did not contain fresh.exe".to_string()))
+}
+
+/// Atomically replace the currently-running executable with `new_binary`.
+///
+/// On unix, `rename` within the same filesystem is atomic, so we can
+/// rename straight over the running exe (the old inode stays valid for the
+/// process that's still executing it until it exits). On Windows the
+/// running exe can't be overwritten directly, so it's renamed aside to a
+/// `.old` sidecar first; that sidecar is left for cleanup on next launch.
+fn replace_running_executable(current_exe: &Path, new_binary: &Path) -> Result<(), SelfUpgradeError> {
+    #[cfg(unix)]
+    {
+        fs::rename(new_binary, current_exe).map_err(|e| SelfUpgradeError::Replace(e.to_string()))
+    }
+    #[cfg(windows)]
+    {
+        let old_sidecar = current_exe.with_extension("exe.old");
+        let _ = fs::remove_file(&old_sidecar);
+        fs::rename(current_exe, &old_sidecar).map_err(|e| SelfUpgradeError::Replace(e.to_string()))?;
+        fs::rename(new_binary, current_exe).map_err(|e| SelfUpgradeError::Replace(e.to_string()))
+    }
+}
+
+/// Download and install the release matching `result.latest_version`,
+/// replacing the running executable in place.
+///
+/// Intended for [`InstallMethod::Unknown`] (manually-downloaded binaries),
+/// where [`InstallMethod::update_command`] has nothing to suggest, or when
+/// the user explicitly asks for an in-place upgrade regardless of install
+/// method.
+pub fn self_upgrade(result: &ReleaseCheckResult) -> Result<(), SelfUpgradeError> {
+    self_upgrade_from_url(result, DEFAULT_RELEASES_URL)
+}
+
+/// Like [`self_upgrade`], but fetching the release JSON from `releases_url`
+/// (split out so tests can point it at a fixture server).
+fn self_upgrade_from_url(result: &ReleaseCheckResult, releases_url: &str) -> Result<(), SelfUpgradeError> {
+    let asset_name = asset_name_for_target();
+    tracing::info!("Self-upgrading to {} using asset '{}'", result.latest_version, asset_name);
+
+    let json = fetch_release_json(releases_url).map_err(SelfUpgradeError::Download)?;
+    let asset_url = parse_asset_url_from_json(&json, &asset_name).map_err(SelfUpgradeError::Download)?;
+    let asset_digest = parse_asset_digest_from_json(&json, &asset_name);
+
+    let current_exe = env::current_exe().map_err(|e| SelfUpgradeError::Replace(e.to_string()))?;
+    let exe_dir = current_exe
+        .parent()
+        .ok_or_else(|| SelfUpgradeError::Replace("current executable has no parent directory".to_string()))?;
+
+    // Download into the same directory as the running exe so the final
+    // rename is guaranteed to be on the same filesystem (and thus atomic).
+    let archive_path = exe_dir.join(format!(".fresh-update-{}", asset_name));
+    download_to_file(&asset_url, &archive_path)?;
+
+    // Verify the archive against GitHub's own published digest before
+    // extracting or installing anything from it. Older releases don't
+    // carry a digest (see `parse_asset_digest_from_json`), in which case
+    // there's nothing to check against and the upgrade proceeds as before.
+    if let Some(expected) = &asset_digest {
+        if let Err(e) = verify_digest(&archive_path, expected) {
+            let _ = fs::remove_file(&archive_path);
+            return Err(e);
+        }
+    }
+
+    let extracted = extract_binary(&archive_path, exe_dir);
+    let _ = fs::remove_file(&archive_path);
+    let extracted = extracted?;
+
+    let result = replace_running_executable(&current_exe, &extracted);
+    let _ = fs::remove_file(&extracted);
+    result
+}
+
+/// How often [`maybe_start_update_check`] will hit the network, by default.
+pub const DEFAULT_CHECK_INTERVAL_HOURS: u64 = 24;
+
+/// Persisted, pluggable environment for the update-check throttle, so tests
+/// can fake the clock and the state file instead of touching the real OS
+/// config dir or the network.
+pub trait UpdateCheckerEnvironment: Send + Sync {
+    /// Read the raw contents of the persisted check-state file, if any.
+    fn read_check_file(&self) -> Option<String>;
+    /// Overwrite the persisted check-state file with `contents`.
+    fn write_check_file(&self, contents: &str);
+    /// The current time, as seen by this environment (fakeable in tests).
+    fn current_time(&self) -> SystemTime;
+}
+
+/// The real environment: stores check state under the OS config dir.
+pub struct OsUpdateCheckerEnvironment {
+    path: PathBuf,
+}
+
+impl OsUpdateCheckerEnvironment {
+    pub fn new() -> Self {
+        Self {
+            path: default_check_file_path(),
+        }
+    }
+}
+
+impl Default for OsUpdateCheckerEnvironment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UpdateCheckerEnvironment for OsUpdateCheckerEnvironment {
+    fn read_check_file(&self) -> Option<String> {
+        fs::read_to_string(&self.path).ok()
+    }
+
+    fn write_check_file(&self, contents: &str) {
+        if let Some(parent) = self.path.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                tracing::debug!("Failed to create update-check state dir {:?}: {}", parent, e);
+                return;
+            }
+        }
+        if let Err(e) = fs::write(&self.path, contents) {
+            tracing::debug!("Failed to write update-check state file {:?}: {}", self.path, e);
+        }
+    }
+
+    fn current_time(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// `$XDG_CONFIG_HOME/fresh/update_check.json` (or the platform equivalent).
+fn default_check_file_path() -> PathBuf {
+    let config_dir = if cfg!(windows) {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else {
+        env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))
+    }
+    .unwrap_or_else(env::temp_dir);
+
+    config_dir.join("fresh").join("update_check.json")
+}
+
+/// The persisted state: when we last checked, and what we found.
+#[derive(Debug, Clone, PartialEq)]
+struct CheckState {
+    last_checked_epoch_secs: u64,
+    last_latest_version: String,
+}
+
+impl CheckState {
+    fn to_json(&self) -> String {
+        format!(
+            r#"{{"last_checked_epoch_secs":{},"last_latest_version":"{}"}}"#,
+            self.last_checked_epoch_secs, self.last_latest_version
+        )
+    }
+
+    /// Manual parse (matching `parse_version_from_json`'s style elsewhere
+    /// in this file) rather than pulling in a JSON crate for two fields.
+    fn from_json(json: &str) -> Option<Self> {
+        let secs_key = "\"last_checked_epoch_secs\":";
+        let secs_start = json.find(secs_key)? + secs_key.len();
+        let secs_end = json[secs_start..].find(|c: char| c == ',' || c == '}')? + secs_start;
+        let last_checked_epoch_secs = json[secs_start..secs_end].trim().parse().ok()?;
+
+        let version_key = "\"last_latest_version\":\"";
+        let version_start = json.find(version_key)? + version_key.len();
+        let version_end = json[version_start..].find('"')? + version_start;
+        let last_latest_version = json[version_start..version_end].to_string();
+
+        Some(Self {
+            last_checked_epoch_secs,
+            last_latest_version,
+        })
+    }
+}
+
+/// What [`maybe_start_update_check`] did.
+pub enum UpdateCheckOutcome {
+    /// The last check was recent enough; here's the result synthesized
+    /// from the persisted state, without touching the network.
+    Cached(ReleaseCheckResult),
+    /// The interval has elapsed (or there was no prior state); a fresh
+    /// background check was started.
+    Started(UpdateCheckHandle),
+}
+
+/// Cheap jitter source so the first network call of a run doesn't line up
+/// with every other instance's startup; not cryptographic, just spread.
+fn jitter_delay() -> Duration {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    Duration::from_millis((nanos % 2_000) as u64)
+}
+
+/// Check for an update at most once every `interval_hours`, reading/writing
+/// state through `env` instead of hitting the network on every launch.
+///
+/// A brief randomized delay precedes the actual network call so it never
+/// competes with editor startup. A failed fetch does not update the
+/// persisted timestamp, so a transient outage doesn't suppress checks for
+/// the rest of the interval.
+pub fn maybe_start_update_check(
+    env: Arc<dyn UpdateCheckerEnvironment>,
+    releases_url: &str,
+    interval_hours: u64,
+) -> UpdateCheckOutcome {
+    let now = env.current_time();
+
+    if let Some(state) = env.read_check_file().and_then(|s| CheckState::from_json(&s)) {
+        let last_checked = UNIX_EPOCH + Duration::from_secs(state.last_checked_epoch_secs);
+        if let Ok(elapsed) = now.duration_since(last_checked) {
+            if elapsed < Duration::from_secs(interval_hours * 3600) {
+                tracing::debug!("Update check throttled; last checked {:?} ago", elapsed);
+                return UpdateCheckOutcome::Cached(ReleaseCheckResult {
+                    update_available: is_newer_version(CURRENT_VERSION, &state.last_latest_version),
+                    latest_version: state.last_latest_version,
+                    install_method: detect_install_method(),
+                });
+            }
+        }
+    }
+
+    let url = releases_url.to_string();
+    let (tx, rx) = mpsc::channel();
+    let thread = thread::spawn(move || {
+        thread::sleep(jitter_delay());
+        let result = check_for_update(&url);
+        if let Ok(ref release) = result {
+            let state = CheckState {
+                last_checked_epoch_secs: now
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0),
+                last_latest_version: release.latest_version.clone(),
+            };
+            env.write_check_file(&state.to_json());
+        }
+        let _ = tx.send(result);
+    });
+
+    UpdateCheckOutcome::Started(UpdateCheckHandle {
+        receiver: rx,
+        thread,
+    })
+}
+
 /// Check for a new release (blocking)
 pub fn check_for_update(releases_url: &str) -> Result<ReleaseCheckResult, String> {
     let latest_version = fetch_latest_version(releases_url)?;
@@ -309,6 +828,30 @@ mod tests {
         assert!(is_newer_version("0.1.26", "0.1.27-beta"));
     }
 
+    #[test]
+    fn test_is_newer_version_prerelease_lower_precedence_than_release() {
+        // Per semver, a prerelease always sorts below its own release.
+        assert!(is_newer_version("1.0.0-alpha", "1.0.0"));
+        assert!(!is_newer_version("1.0.0", "1.0.0-alpha"));
+    }
+
+    #[test]
+    fn test_is_newer_version_prerelease_identifier_ordering() {
+        // alpha < alpha.1 < alpha.beta < beta < beta.2 < beta.11 < rc.1
+        assert!(is_newer_version("1.0.0-alpha", "1.0.0-alpha.1"));
+        assert!(is_newer_version("1.0.0-alpha.1", "1.0.0-alpha.beta"));
+        assert!(is_newer_version("1.0.0-alpha.beta", "1.0.0-beta"));
+        assert!(is_newer_version("1.0.0-beta", "1.0.0-beta.2"));
+        assert!(is_newer_version("1.0.0-beta.2", "1.0.0-beta.11"));
+        assert!(is_newer_version("1.0.0-beta.11", "1.0.0-rc.1"));
+    }
+
+    #[test]
+    fn test_is_newer_version_ignores_build_metadata() {
+        assert!(!is_newer_version("1.0.0+build.1", "1.0.0+build.2"));
+        assert!(is_newer_version("1.0.0+build.1", "1.0.1+build.1"));
+    }
+
     #[test]
     fn test_detect_install_method_homebrew_macos() {
         let path = PathBuf::from("/opt/homebrew/Cellar/fresh/0.1.26/bin/fresh");
@@ -397,6 +940,113 @@ mod tests {
         assert_eq!(parse_version_from_json(json).unwrap(), "0.2.0");
     }
 
+    #[test]
+    fn test_parse_asset_url_from_json() {
+        let json = r#"{
+            "tag_name": "v0.2.0",
+            "assets": [
+                {
+                    "name": "fresh-x86_64-apple-darwin.tar.gz",
+                    "browser_download_url": "https://github.com/sinelaw/fresh/releases/download/v0.2.0/fresh-x86_64-apple-darwin.tar.gz"
+                },
+                {
+                    "name": "fresh-x86_64-unknown-linux-gnu.tar.gz",
+                    "browser_download_url": "https://github.com/sinelaw/fresh/releases/download/v0.2.0/fresh-x86_64-unknown-linux-gnu.tar.gz"
+                }
+            ]
+        }"#;
+
+        let url = parse_asset_url_from_json(json, "fresh-x86_64-unknown-linux-gnu.tar.gz").unwrap();
+        assert_eq!(
+            url,
+            "https://github.com/sinelaw/fresh/releases/download/v0.2.0/fresh-x86_64-unknown-linux-gnu.tar.gz"
+        );
+    }
+
+    #[test]
+    fn test_parse_asset_url_from_json_missing_asset() {
+        let json = r#"{"assets": [{"name": "other.zip", "browser_download_url": "https://example.com/other.zip"}]}"#;
+        assert!(parse_asset_url_from_json(json, "fresh-x86_64-unknown-linux-gnu.tar.gz").is_err());
+    }
+
+    #[test]
+    fn test_parse_asset_digest_from_json() {
+        let json = r#"{
+            "assets": [
+                {
+                    "name": "fresh-x86_64-unknown-linux-gnu.tar.gz",
+                    "browser_download_url": "https://example.com/fresh.tar.gz",
+                    "digest": "sha256:e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+                }
+            ]
+        }"#;
+
+        let digest = parse_asset_digest_from_json(json, "fresh-x86_64-unknown-linux-gnu.tar.gz");
+        assert_eq!(
+            digest,
+            Some("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_asset_digest_from_json_missing_digest() {
+        let json = r#"{"assets": [{"name": "other.zip", "browser_download_url": "https://example.com/other.zip"}]}"#;
+        assert_eq!(parse_asset_digest_from_json(json, "other.zip"), None);
+    }
+
+    #[test]
+    fn test_sha256_hex_matches_known_vector() {
+        // sha256("") = e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855
+        assert_eq!(
+            sha256_hex(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn test_verify_digest_accepts_matching_archive() {
+        let path = env::temp_dir().join("fresh-self-upgrade-test-accepts.tar.gz");
+        fs::write(&path, b"a real release archive").unwrap();
+
+        let expected = sha256_hex(b"a real release archive");
+        assert!(verify_digest(&path, &expected).is_ok());
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_verify_digest_rejects_tampered_archive() {
+        let path = env::temp_dir().join("fresh-self-upgrade-test-rejects.tar.gz");
+        fs::write(&path, b"a tampered-with release archive").unwrap();
+
+        let expected = sha256_hex(b"a real release archive");
+        let result = verify_digest(&path, &expected);
+        assert!(matches!(result, Err(SelfUpgradeError::Integrity(_))));
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_verify_digest_comparison_is_case_insensitive() {
+        let path = env::temp_dir().join("fresh-self-upgrade-test-case.tar.gz");
+        fs::write(&path, b"a real release archive").unwrap();
+
+        let expected = sha256_hex(b"a real release archive").to_uppercase();
+        assert!(verify_digest(&path, &expected).is_ok());
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_asset_name_for_target_matches_platform_extension() {
+        let name = asset_name_for_target();
+        if cfg!(windows) {
+            assert!(name.ends_with(".zip"), "expected .zip asset name, got {name}");
+        } else {
+            assert!(name.ends_with(".tar.gz"), "expected .tar.gz asset name, got {name}");
+        }
+    }
+
     #[test]
     fn test_update_commands() {
         assert_eq!(
@@ -434,4 +1084,87 @@ mod tests {
         let version = parse_version_from_json(json).unwrap();
         assert!(is_newer_version(CURRENT_VERSION, &version));
     }
+
+    #[test]
+    fn test_check_state_json_round_trip() {
+        let state = CheckState {
+            last_checked_epoch_secs: 1_700_000_000,
+            last_latest_version: "1.2.3".to_string(),
+        };
+        let parsed = CheckState::from_json(&state.to_json()).unwrap();
+        assert_eq!(parsed, state);
+    }
+
+    /// In-memory [`UpdateCheckerEnvironment`] for exercising the throttle
+    /// without touching the real filesystem or clock.
+    struct FakeEnv {
+        file: std::sync::RwLock<Option<String>>,
+        now: SystemTime,
+    }
+
+    impl UpdateCheckerEnvironment for FakeEnv {
+        fn read_check_file(&self) -> Option<String> {
+            self.file.read().unwrap().clone()
+        }
+
+        fn write_check_file(&self, contents: &str) {
+            *self.file.write().unwrap() = Some(contents.to_string());
+        }
+
+        fn current_time(&self) -> SystemTime {
+            self.now
+        }
+    }
+
+    #[test]
+    fn test_maybe_start_update_check_returns_cached_within_interval() {
+        let now = SystemTime::now();
+        let state = CheckState {
+            last_checked_epoch_secs: now.duration_since(UNIX_EPOCH).unwrap().as_secs() - 60,
+            last_latest_version: "99.0.0".to_string(),
+        };
+        let env = Arc::new(FakeEnv {
+            file: std::sync::RwLock::new(Some(state.to_json())),
+            now,
+        });
+
+        match maybe_start_update_check(env, "https://example.com/releases", 24) {
+            UpdateCheckOutcome::Cached(result) => {
+                assert_eq!(result.latest_version, "99.0.0");
+                assert!(result.update_available);
+            }
+            UpdateCheckOutcome::Started(_) => panic!("expected a cached result, not a new check"),
+        }
+    }
+
+    #[test]
+    fn test_maybe_start_update_check_starts_when_no_prior_state() {
+        let env = Arc::new(FakeEnv {
+            file: std::sync::RwLock::new(None),
+            now: SystemTime::now(),
+        });
+
+        match maybe_start_update_check(env, "https://example.com/releases", 24) {
+            UpdateCheckOutcome::Started(_) => {}
+            UpdateCheckOutcome::Cached(_) => panic!("expected a fresh check with no prior state"),
+        }
+    }
+
+    #[test]
+    fn test_maybe_start_update_check_starts_after_interval_elapses() {
+        let now = SystemTime::now();
+        let state = CheckState {
+            last_checked_epoch_secs: now.duration_since(UNIX_EPOCH).unwrap().as_secs() - 25 * 3600,
+            last_latest_version: "0.1.0".to_string(),
+        };
+        let env = Arc::new(FakeEnv {
+            file: std::sync::RwLock::new(Some(state.to_json())),
+            now,
+        });
+
+        match maybe_start_update_check(env, "https://example.com/releases", 24) {
+            UpdateCheckOutcome::Started(_) => {}
+            UpdateCheckOutcome::Cached(_) => panic!("expected the stale interval to trigger a new check"),
+        }
+    }
 }
@@ -5,8 +5,122 @@
 //! that existed with the Lua plugin system.
 
 use anyhow::{anyhow, Result};
-use deno_core::{extension, op2, FastString, JsRuntime, RuntimeOptions};
+use deno_core::{extension, op2, FastString, JsRuntime, OpState, RuntimeOptions};
+use std::cell::RefCell;
 use std::rc::Rc;
+use std::sync::mpsc;
+use tokio::sync::oneshot;
+
+/// A request for live editor state, sent by a plugin op to whatever is
+/// draining the matching [`EditorApiProxy`] (normally the main editor loop,
+/// which may run on a different thread than the JS runtime).
+pub enum EditorApiRequest {
+    GetBufferText {
+        buffer_id: u32,
+        reply: oneshot::Sender<Option<String>>,
+    },
+    ReplaceRange {
+        buffer_id: u32,
+        start: u32,
+        end: u32,
+        text: String,
+        reply: oneshot::Sender<bool>,
+    },
+    GetCursor {
+        buffer_id: u32,
+        reply: oneshot::Sender<Option<(u32, u32)>>,
+    },
+    SetCursor {
+        buffer_id: u32,
+        line: u32,
+        column: u32,
+        reply: oneshot::Sender<bool>,
+    },
+    GetActiveBufferId {
+        reply: oneshot::Sender<u32>,
+    },
+}
+
+/// The sending half plugins use to reach live editor state; cloned into the
+/// runtime's [`OpState`] so every op can reach it.
+pub type EditorApiSender = mpsc::Sender<EditorApiRequest>;
+
+/// Implemented by whatever owns live buffer/cursor state: the editor's main
+/// loop in production, a fake in-memory buffer in tests.
+pub trait EditorApi {
+    fn get_buffer_text(&self, buffer_id: u32) -> Option<String>;
+    fn replace_range(&mut self, buffer_id: u32, start: u32, end: u32, text: &str) -> bool;
+    fn get_cursor(&self, buffer_id: u32) -> Option<(u32, u32)>;
+    fn set_cursor(&mut self, buffer_id: u32, line: u32, column: u32) -> bool;
+    fn active_buffer_id(&self) -> u32;
+}
+
+/// The main-thread side of the plugin <-> editor channel. Owns the
+/// receiving end; each call to [`drain`](Self::drain) answers every request
+/// the TypeScript runtime has queued since the previous drain.
+pub struct EditorApiProxy {
+    receiver: mpsc::Receiver<EditorApiRequest>,
+}
+
+impl EditorApiProxy {
+    /// Answer every pending request against `api`, without blocking if none
+    /// are queued. Intended to be called once per main-loop tick.
+    pub fn drain(&self, api: &mut impl EditorApi) {
+        while let Ok(request) = self.receiver.try_recv() {
+            match request {
+                EditorApiRequest::GetBufferText { buffer_id, reply } => {
+                    let _ = reply.send(api.get_buffer_text(buffer_id));
+                }
+                EditorApiRequest::ReplaceRange {
+                    buffer_id,
+                    start,
+                    end,
+                    text,
+                    reply,
+                } => {
+                    let _ = reply.send(api.replace_range(buffer_id, start, end, &text));
+                }
+                EditorApiRequest::GetCursor { buffer_id, reply } => {
+                    let _ = reply.send(api.get_cursor(buffer_id));
+                }
+                EditorApiRequest::SetCursor {
+                    buffer_id,
+                    line,
+                    column,
+                    reply,
+                } => {
+                    let _ = reply.send(api.set_cursor(buffer_id, line, column));
+                }
+                EditorApiRequest::GetActiveBufferId { reply } => {
+                    let _ = reply.send(api.active_buffer_id());
+                }
+            }
+        }
+    }
+}
+
+/// Send `request` (built from a fresh oneshot pair by `build`) over the
+/// channel stashed in `state`, then await the reply. Returns `None` if no
+/// channel was wired (e.g. a runtime created without editor access) or if
+/// the receiving side was dropped before replying.
+async fn proxy_request<T>(
+    state: Rc<RefCell<OpState>>,
+    build: impl FnOnce(oneshot::Sender<T>) -> EditorApiRequest,
+) -> Option<T> {
+    let sender = state.borrow().try_borrow::<EditorApiSender>().cloned()?;
+    let (reply_tx, reply_rx) = oneshot::channel();
+    sender.send(build(reply_tx)).ok()?;
+    reply_rx.await.ok()
+}
+
+/// Pack a cursor position into the i64 wire format shared with the
+/// extension host's view-transform ABI: `-1` means "no such buffer/cursor".
+fn pack_cursor(cursor: Option<(u32, u32)>) -> i64 {
+    match cursor {
+        Some((line, column)) => ((line as i64) << 32) | (column as i64),
+        None => -1,
+    }
+}
 
 /// Custom ops for the Fresh editor API
 #[op2(fast)]
@@ -20,32 +134,118 @@ fn op_fresh_debug(#[string] message: String) {
     tracing::debug!("TypeScript plugin: {}", message);
 }
 
-#[op2(fast)]
-fn op_fresh_get_active_buffer_id() -> u32 {
-    // Placeholder - will be connected to actual editor state
-    0
+#[op2(async)]
+async fn op_fresh_get_active_buffer_id(state: Rc<RefCell<OpState>>) -> u32 {
+    proxy_request(state, |reply| EditorApiRequest::GetActiveBufferId { reply })
+        .await
+        .unwrap_or(0)
+}
+
+#[op2(async)]
+#[string]
+async fn op_fresh_get_buffer_text(state: Rc<RefCell<OpState>>, buffer_id: u32) -> String {
+    proxy_request(state, |reply| EditorApiRequest::GetBufferText { buffer_id, reply })
+        .await
+        .flatten()
+        .unwrap_or_default()
+}
+
+#[op2(async)]
+async fn op_fresh_replace_range(
+    state: Rc<RefCell<OpState>>,
+    buffer_id: u32,
+    start: u32,
+    end: u32,
+    #[string] text: String,
+) -> bool {
+    proxy_request(state, |reply| EditorApiRequest::ReplaceRange {
+        buffer_id,
+        start,
+        end,
+        text,
+        reply,
+    })
+    .await
+    .unwrap_or(false)
+}
+
+#[op2(async)]
+async fn op_fresh_get_cursor(state: Rc<RefCell<OpState>>, buffer_id: u32) -> i64 {
+    let cursor = proxy_request(state, |reply| EditorApiRequest::GetCursor { buffer_id, reply }).await;
+    pack_cursor(cursor.flatten())
+}
+
+#[op2(async)]
+async fn op_fresh_set_cursor(state: Rc<RefCell<OpState>>, buffer_id: u32, line: u32, column: u32) -> bool {
+    proxy_request(state, |reply| EditorApiRequest::SetCursor {
+        buffer_id,
+        line,
+        column,
+        reply,
+    })
+    .await
+    .unwrap_or(false)
 }
 
 // Define the extension with our ops
 extension!(
     fresh_runtime,
-    ops = [op_fresh_set_status, op_fresh_debug, op_fresh_get_active_buffer_id],
+    ops = [
+        op_fresh_set_status,
+        op_fresh_debug,
+        op_fresh_get_active_buffer_id,
+        op_fresh_get_buffer_text,
+        op_fresh_replace_range,
+        op_fresh_get_cursor,
+        op_fresh_set_cursor,
+    ],
 );
 
+/// Names of the custom ops registered on every [`TypeScriptRuntime`], kept
+/// in sync with the `extension!` ops list above for diagnostics reporting.
+pub const REGISTERED_OP_NAMES: &[&str] = &[
+    "op_fresh_set_status",
+    "op_fresh_debug",
+    "op_fresh_get_active_buffer_id",
+    "op_fresh_get_buffer_text",
+    "op_fresh_replace_range",
+    "op_fresh_get_cursor",
+    "op_fresh_set_cursor",
+];
+
 /// TypeScript plugin runtime
 pub struct TypeScriptRuntime {
     js_runtime: JsRuntime,
 }
 
 impl TypeScriptRuntime {
-    /// Create a new TypeScript runtime
+    /// Create a new TypeScript runtime with no connection to live editor
+    /// state; buffer/cursor ops resolve to their "nothing there" defaults.
     pub fn new() -> Result<Self> {
+        Self::with_editor_channel(None)
+    }
+
+    /// Create a runtime together with the main-thread proxy it talks to, for
+    /// wiring a plugin host up to the real editor.
+    pub fn with_editor_api() -> Result<(Self, EditorApiProxy)> {
+        let (sender, receiver) = mpsc::channel();
+        let runtime = Self::with_editor_channel(Some(sender))?;
+        Ok((runtime, EditorApiProxy { receiver }))
+    }
+
+    /// Create a runtime wired to live editor state via `sender`, or with no
+    /// channel at all if `None`.
+    pub fn with_editor_channel(sender: Option<EditorApiSender>) -> Result<Self> {
         let mut js_runtime = JsRuntime::new(RuntimeOptions {
-            module_loader: Some(Rc::new(deno_core::FsModuleLoader)),
+            module_loader: Some(Rc::new(crate::plugin_cache::CachedRemoteModuleLoader::new())),
             extensions: vec![fresh_runtime::init_ops()],
             ..Default::default()
         });
 
+        if let Some(sender) = sender {
+            js_runtime.op_state().borrow_mut().put(sender);
+        }
+
         // Set up the global editor API
         js_runtime
             .execute_script(
@@ -61,9 +261,40 @@ impl TypeScriptRuntime {
                     debug(message) {
                         core.ops.op_fresh_debug(message);
                     },
-                    getActiveBufferId() {
-                        return core.ops.op_fresh_get_active_buffer_id();
+                    async getActiveBufferId() {
+                        return await core.ops.op_fresh_get_active_buffer_id();
+                    },
+                    async getBufferText(bufferId) {
+                        return await core.ops.op_fresh_get_buffer_text(bufferId);
+                    },
+                    async replaceRange(bufferId, start, end, text) {
+                        return await core.ops.op_fresh_replace_range(bufferId, start, end, text);
                     },
+                    async getCursor(bufferId) {
+                        const packed = await core.ops.op_fresh_get_cursor(bufferId);
+                        if (packed < 0n) return null;
+                        return { line: Number(packed >> 32n), column: Number(packed & 0xffffffffn) };
+                    },
+                    async setCursor(bufferId, line, column) {
+                        return await core.ops.op_fresh_set_cursor(bufferId, line, column);
+                    },
+                };
+
+                // Lifecycle event targets: a plugin registers handlers with
+                // editor.onLoad/onUnload/onActivate; the runtime dispatches
+                // them via the internal __fresh_dispatch hook below.
+                const __listeners = { load: [], unload: [], activate: [] };
+                editor.onLoad = (handler) => { __listeners.load.push(handler); };
+                editor.onUnload = (handler) => { __listeners.unload.push(handler); };
+                editor.onActivate = (handler) => { __listeners.activate.push(handler); };
+
+                globalThis.__fresh_dispatch = async (event) => {
+                    for (const handler of __listeners[event] ?? []) {
+                        const result = handler();
+                        if (result instanceof Promise) {
+                            await result;
+                        }
+                    }
                 };
 
                 // Make editor globally available
@@ -101,11 +332,35 @@ impl TypeScriptRuntime {
         )
         .map_err(|e| anyhow!("Failed to resolve module path '{}': {}", path, e))?;
 
+        self.evaluate_main_module(main_module).await?;
+        self.dispatch_event("load").await
+    }
+
+    /// Install `source` into the plugin cache (if not already cached) and
+    /// load it as the main module, via the same `https://`-aware loader
+    /// `load_module` uses for its transitive imports.
+    pub async fn load_remote_module(&mut self, source: &crate::plugin_cache::PluginSource) -> Result<()> {
+        let path = crate::plugin_cache::install_plugin(source, None)?;
+        let main_module = deno_core::resolve_path(
+            path.to_string_lossy().as_ref(),
+            &std::env::current_dir().map_err(|e| anyhow!("Failed to get cwd: {}", e))?,
+        )
+        .map_err(|e| anyhow!("Failed to resolve cached plugin path '{:?}': {}", path, e))?;
+
+        self.evaluate_main_module(main_module).await?;
+        self.dispatch_event("load").await
+    }
+
+    /// Load and run `main_module` as the runtime's main ES module, driving
+    /// the event loop to completion. Shared by [`load_module`](Self::load_module)
+    /// and [`load_remote_module`](Self::load_remote_module), which only
+    /// differ in how they resolve the module specifier.
+    async fn evaluate_main_module(&mut self, main_module: deno_core::ModuleSpecifier) -> Result<()> {
         let mod_id = self
             .js_runtime
             .load_main_es_module(&main_module)
             .await
-            .map_err(|e| anyhow!("Failed to load module '{}': {}", path, e))?;
+            .map_err(|e| anyhow!("Failed to load module '{}': {}", main_module, e))?;
 
         let result = self.js_runtime.mod_evaluate(mod_id);
 
@@ -141,6 +396,51 @@ impl TypeScriptRuntime {
 
         self.execute_script("<action>", &code).await
     }
+
+    /// Fire every handler registered for `event` ("load", "unload", or
+    /// "activate") via `editor.on<Event>`, awaiting any promise a handler
+    /// returns, the same way [`execute_action`](Self::execute_action) awaits
+    /// a plugin's action function.
+    async fn dispatch_event(&mut self, event: &str) -> Result<()> {
+        let code = format!("(async () => {{ await globalThis.__fresh_dispatch('{event}'); }})();");
+        self.execute_script("<lifecycle_dispatch>", &code).await
+    }
+
+    /// Fire the `onActivate` handlers, e.g. when a plugin's view mode
+    /// becomes active.
+    pub async fn activate(&mut self) -> Result<()> {
+        self.dispatch_event("activate").await
+    }
+
+    /// Fire the `onUnload` handlers so a plugin can run async cleanup
+    /// before it goes away. Safe to call more than once; a plugin with no
+    /// `onUnload` handlers is a no-op.
+    pub async fn unload(&mut self) -> Result<()> {
+        self.dispatch_event("unload").await
+    }
+}
+
+impl Drop for TypeScriptRuntime {
+    fn drop(&mut self) {
+        // Best-effort: `unload()` is async and Drop isn't, so this only
+        // fires when we're already inside a multi-threaded Tokio runtime,
+        // where `block_in_place` can safely pause this thread without
+        // reentering a single-threaded executor. Callers that need
+        // guaranteed cleanup (the common case in short-lived tests, which
+        // default to the current-thread runtime) should call `unload()`
+        // explicitly before dropping.
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread {
+                tokio::task::block_in_place(|| {
+                    handle.block_on(async {
+                        if let Err(e) = self.dispatch_event("unload").await {
+                            tracing::warn!("Error dispatching onUnload during drop: {}", e);
+                        }
+                    });
+                });
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -242,4 +542,165 @@ mod tests {
         let result = runtime.execute_action("my_async_action").await;
         assert!(result.is_ok(), "Failed to execute async action: {:?}", result);
     }
+
+    /// A single in-memory buffer standing in for the real editor state, so
+    /// the plugin <-> editor channel can be exercised without a live editor.
+    struct FakeBuffer {
+        text: String,
+        cursor: (u32, u32),
+    }
+
+    impl EditorApi for FakeBuffer {
+        fn get_buffer_text(&self, buffer_id: u32) -> Option<String> {
+            (buffer_id == 0).then(|| self.text.clone())
+        }
+
+        fn replace_range(&mut self, buffer_id: u32, start: u32, end: u32, text: &str) -> bool {
+            if buffer_id != 0 {
+                return false;
+            }
+            let (start, end) = (start as usize, end as usize);
+            if end > self.text.len() || start > end {
+                return false;
+            }
+            self.text.replace_range(start..end, text);
+            true
+        }
+
+        fn get_cursor(&self, buffer_id: u32) -> Option<(u32, u32)> {
+            (buffer_id == 0).then_some(self.cursor)
+        }
+
+        fn set_cursor(&mut self, buffer_id: u32, line: u32, column: u32) -> bool {
+            if buffer_id != 0 {
+                return false;
+            }
+            self.cursor = (line, column);
+            true
+        }
+
+        fn active_buffer_id(&self) -> u32 {
+            0
+        }
+    }
+
+    #[tokio::test]
+    async fn test_plugin_reads_and_mutates_fake_buffer_through_channel() {
+        let (mut runtime, proxy) = TypeScriptRuntime::with_editor_api().unwrap();
+        let mut buffer = FakeBuffer {
+            text: "hello world".to_string(),
+            cursor: (0, 0),
+        };
+
+        // The real main loop drains the proxy once per tick; here we just
+        // poll it on a background thread for as long as the script runs.
+        let stop = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let stop_reader = stop.clone();
+        let drainer = std::thread::spawn(move || {
+            while !stop_reader.load(std::sync::atomic::Ordering::Relaxed) {
+                proxy.drain(&mut buffer);
+                std::thread::sleep(std::time::Duration::from_millis(1));
+            }
+            proxy.drain(&mut buffer);
+            buffer
+        });
+
+        let result = runtime
+            .execute_script(
+                "<plugin_buffer_access>",
+                r#"
+                (async () => {
+                    const id = await editor.getActiveBufferId();
+                    const text = await editor.getBufferText(id);
+                    globalThis.__observedText = text;
+                    globalThis.__replaceOk = await editor.replaceRange(id, 6, 11, "there");
+                    globalThis.__setCursorOk = await editor.setCursor(id, 1, 2);
+                })();
+                "#,
+            )
+            .await;
+        assert!(result.is_ok(), "plugin script failed: {:?}", result);
+
+        stop.store(true, std::sync::atomic::Ordering::Relaxed);
+        let buffer = drainer.join().unwrap();
+        assert_eq!(buffer.text, "hello there");
+        assert_eq!(buffer.cursor, (1, 2));
+    }
+
+    #[tokio::test]
+    async fn test_ops_without_editor_channel_return_defaults() {
+        let mut runtime = TypeScriptRuntime::new().unwrap();
+        let result = runtime
+            .execute_script(
+                "<plugin_no_channel>",
+                r#"
+                (async () => {
+                    const text = await editor.getBufferText(0);
+                    if (text !== "") throw new Error("expected empty text with no channel wired");
+                    const cursor = await editor.getCursor(0);
+                    if (cursor !== null) throw new Error("expected null cursor with no channel wired");
+                })();
+                "#,
+            )
+            .await;
+        assert!(result.is_ok(), "plugin script failed: {:?}", result);
+    }
+
+    #[tokio::test]
+    async fn test_on_load_handler_runs_when_load_is_dispatched() {
+        let mut runtime = TypeScriptRuntime::new().unwrap();
+        runtime
+            .execute_script(
+                "<register_on_load>",
+                r#"
+                globalThis.__loaded = false;
+                editor.onLoad(() => { globalThis.__loaded = true; });
+                "#,
+            )
+            .await
+            .unwrap();
+
+        assert!(runtime.dispatch_event("load").await.is_ok());
+
+        let loaded: bool = runtime
+            .js_runtime
+            .execute_script("<check_loaded>", "globalThis.__loaded".to_string())
+            .map(|v| {
+                let scope = &mut runtime.js_runtime.handle_scope();
+                v.open(scope).is_true()
+            })
+            .unwrap_or(false);
+        assert!(loaded, "expected onLoad handler to have run");
+    }
+
+    #[tokio::test]
+    async fn test_on_unload_handlers_fire_and_await_async_cleanup() {
+        let mut runtime = TypeScriptRuntime::new().unwrap();
+        runtime
+            .execute_script(
+                "<register_on_unload>",
+                r#"
+                globalThis.__cleanedUp = false;
+                editor.onUnload(async () => {
+                    await Promise.resolve();
+                    globalThis.__cleanedUp = true;
+                });
+                "#,
+            )
+            .await
+            .unwrap();
+
+        let result = runtime.unload().await;
+        assert!(result.is_ok(), "unload() failed: {:?}", result);
+
+        let cleaned_up: bool = runtime
+            .js_runtime
+            .execute_script("<check_cleaned_up>", "globalThis.__cleanedUp".to_string())
+            .map(|v| {
+                let scope = &mut runtime.js_runtime.handle_scope();
+                v.open(scope).is_true()
+            })
+            .unwrap_or(false);
+        assert!(cleaned_up, "expected onUnload handler's async cleanup to have run");
+    }
 }